@@ -0,0 +1,181 @@
+// src/affix.rs
+//! Combined prefix/suffix matching, spaCy's `prefix_search`/`suffix_search`.
+//!
+//! Instead of trying every affix pattern in turn, consecutive patterns of the same class
+//! (see `classify`) are matched together: literal runs with one Aho-Corasick automaton,
+//! other runs as one alternation anchored at the start for prefixes (`^(?:p1|p2|..)`) and
+//! at the end for suffixes (`(?:s1|s2|..)$`), compiled with the fast `regex::Regex`, or
+//! with `fancy_regex` for runs that need look-around (e.g. `(?<=[0-9])\+`).
+//! Splitting into runs keeps the rule order, so the result is the same as one alternation:
+//! the first pattern matching at the start wins for prefixes, the leftmost match ending at
+//! the end wins for suffixes (ties going to the earlier pattern).
+
+
+use crate::classify::{self, ClassificationReport, LiteralSet, Route, RuleRegex};
+use crate::error::{RuleKind, TokenizerError};
+
+#[derive(Clone)]
+enum AffixRun {
+    Literal(LiteralSet),
+    Pattern {
+        combined: RuleRegex,
+        /// Index of the run's first pattern in the rule list.
+        first_index: usize,
+        /// Each pattern of the run anchored on its own; only used to tell which pattern matched.
+        members: Vec<RuleRegex>,
+    },
+}
+
+/// A prefix or suffix found by `AffixMatcher`: byte span in the searched text.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct AffixMatch {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    run: usize,
+    /// Known up front for literal runs.
+    pattern_index: Option<usize>,
+}
+
+/// The compiled prefix (or suffix) rules.
+#[derive(Clone)]
+pub(crate) struct AffixMatcher {
+    runs: Vec<AffixRun>,
+    num_patterns: usize,
+}
+
+impl AffixMatcher {
+    /// Compiles `patterns` for `RuleKind::Prefix` or `RuleKind::Suffix`, adding how each one
+    /// was classified to `report`.
+    ///
+    /// Every pattern is validated with `fancy_regex` first, so an invalid one is reported on its own.
+    pub(crate) fn new(kind: RuleKind, patterns: &[String], report: &mut ClassificationReport) -> Result<Self, TokenizerError> {
+        let anchor = |body: &str| match kind {
+            RuleKind::Suffix => format!("(?:{})$", body),
+            _ => format!("^(?:{})", body),
+        };
+
+        // Consecutive patterns with the same class form a run: (first_index, routes).
+        let report_start = report.patterns.len();
+        let mut grouped: Vec<(usize, Vec<(usize, Route)>)> = Vec::new();
+        for (i, pattern) in patterns.iter().enumerate() {
+            if let Err(e) = classify::fancy_regex(pattern) {
+                return Err(TokenizerError::InvalidPattern { kind, index: i, pattern: pattern.clone(), source: e });
+            }
+            let route = classify::route(pattern);
+            report.patterns.push(classify::classification(kind, i, pattern, &route));
+            match grouped.last_mut() {
+                Some((_, run)) if run[0].1.class() == route.class() => run.push((i, route)),
+                _ => grouped.push((i, vec![(i, route)])),
+            }
+        }
+
+        let mut runs = Vec::with_capacity(grouped.len());
+        for (first_index, run) in grouped {
+            if let Route::Literal(_) = run[0].1 {
+                let literals: Vec<(usize, Vec<String>)> = run.into_iter()
+                    .map(|(i, route)| match route {
+                        Route::Literal(strings) => (i, strings),
+                        _ => unreachable!("runs only hold one class"),
+                    })
+                    .collect();
+                runs.push(AffixRun::Literal(match kind {
+                    RuleKind::Suffix => LiteralSet::suffix(&literals)?,
+                    _ => LiteralSet::prefix(&literals)?,
+                }));
+                continue;
+            }
+            let alternation = run.iter().map(|(i, _)| format!("(?:{})", patterns[*i])).collect::<Vec<_>>().join("|");
+            let members = run.iter()
+                .map(|(i, route)| RuleRegex::new(kind, *i, &anchor(&patterns[*i]), &mut route.clone()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut combined_route = run[0].1.clone();
+            let combined = RuleRegex::new(kind, first_index, &anchor(&alternation), &mut combined_route)?;
+            // The run is matched with `combined`: report its engine if `regex` refused it.
+            if combined_route.class() != run[0].1.class() {
+                for (i, _) in &run {
+                    report.patterns[report_start + i] = classify::classification(kind, *i, &patterns[*i], &combined_route);
+                }
+            }
+            runs.push(AffixRun::Pattern { combined, first_index, members });
+        }
+        Ok(AffixMatcher { runs, num_patterns: patterns.len() })
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.num_patterns
+    }
+
+    /// Finds the prefix of `text`: the match of the first pattern that matches at the start.
+    /// An empty match counts as no prefix, as in spaCy.
+    ///
+    /// A `fancy_regex` run that fails at match time is passed to `on_error` (with the index of its
+    /// first pattern and the combined pattern) and skipped if `on_error` returns `Ok`.
+    pub(crate) fn find_prefix<F>(&self, text: &str, mut on_error: F) -> Result<Option<AffixMatch>, TokenizerError>
+    where
+        F: FnMut(usize, &str, fancy_regex::Error) -> Result<(), TokenizerError>,
+    {
+        for (run_idx, run) in self.runs.iter().enumerate() {
+            match run {
+                AffixRun::Literal(literals) => {
+                    if let Some((end, index)) = literals.find_prefix(text) {
+                        return Ok(Some(AffixMatch { start: 0, end, run: run_idx, pattern_index: Some(index) }));
+                    }
+                }
+                AffixRun::Pattern { combined, first_index, .. } => match combined.find_at(text, 0) {
+                    Ok(Some((_, 0))) => return Ok(None),
+                    Ok(Some((start, end))) => return Ok(Some(AffixMatch { start, end, run: run_idx, pattern_index: None })),
+                    Ok(None) => {}
+                    Err(e) => Self::report(combined, *first_index, *e, &mut on_error)?,
+                },
+            }
+        }
+        Ok(None)
+    }
+
+    /// Finds the suffix of `text`: the leftmost match that ends at the end of `text`.
+    /// An empty match counts as no suffix, as in spaCy. Runtime failures are handled as in `find_prefix`.
+    pub(crate) fn find_suffix<F>(&self, text: &str, mut on_error: F) -> Result<Option<AffixMatch>, TokenizerError>
+    where
+        F: FnMut(usize, &str, fancy_regex::Error) -> Result<(), TokenizerError>,
+    {
+        let mut best: Option<AffixMatch> = None;
+        for (run_idx, run) in self.runs.iter().enumerate() {
+            let found = match run {
+                AffixRun::Literal(literals) => literals.find_suffix(text)
+                    .map(|(start, index)| AffixMatch { start, end: text.len(), run: run_idx, pattern_index: Some(index) }),
+                AffixRun::Pattern { combined, first_index, .. } => match combined.find_at(text, 0) {
+                    Ok(found) => found.map(|(start, end)| AffixMatch { start, end, run: run_idx, pattern_index: None }),
+                    Err(e) => {
+                        Self::report(combined, *first_index, *e, &mut on_error)?;
+                        None
+                    }
+                },
+            };
+            // A later run only wins with a strictly earlier start.
+            if let Some(found) = found.filter(|f| best.is_none_or(|b| f.start < b.start)) {
+                best = Some(found);
+            }
+        }
+        Ok(best.filter(|m| m.end > m.start))
+    }
+
+    /// The index (in the rule list) of the pattern that produced `mat`, found in `text`.
+    pub(crate) fn pattern_index(&self, text: &str, mat: &AffixMatch) -> usize {
+        if let Some(index) = mat.pattern_index {
+            return index;
+        }
+        match &self.runs[mat.run] {
+            AffixRun::Pattern { first_index, members, .. } => members.iter()
+                .position(|member| matches!(member.find_at(text, mat.start), Ok(Some((start, _))) if start == mat.start))
+                .map_or(*first_index, |i| first_index + i),
+            AffixRun::Literal(_) => unreachable!("literal matches carry their pattern index"),
+        }
+    }
+
+    fn report<F>(combined: &RuleRegex, first_index: usize, err: fancy_regex::Error, on_error: &mut F) -> Result<(), TokenizerError>
+    where
+        F: FnMut(usize, &str, fancy_regex::Error) -> Result<(), TokenizerError>,
+    {
+        on_error(first_index, combined.as_str(), err)
+    }
+}
@@ -0,0 +1,119 @@
+// src/cache.rs
+//! Memoized chunk splits, as spaCy's tokenizer cache.
+//!
+//! Natural text repeats the same whitespace-delimited strings over and over, and a chunk's
+//! split only depends on the rules. `ChunkCache` maps chunk text to the byte spans of its
+//! tokens, so a repeated chunk skips rule evaluation entirely.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::BuildHasher;
+use std::collections::hash_map::RandomState;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Default number of chunks the cache holds.
+pub const DEFAULT_CACHE_CAPACITY: usize = 100_000;
+
+/// Most independently locked parts of the cache, so worker threads rarely contend.
+const MAX_SHARDS: usize = 16;
+
+/// A snapshot of the chunk cache counters, from `Tokenizer::chunk_cache_stats`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Chunks currently cached.
+    pub entries: usize,
+    pub capacity: usize,
+}
+
+impl CacheStats {
+    /// Share of lookups answered from the cache (0 when there were none).
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 { 0.0 } else { self.hits as f64 / lookups as f64 }
+    }
+}
+
+impl fmt::Display for CacheStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "chunk cache: {} hits, {} misses ({:.1}% hit rate), {}/{} entries",
+            self.hits, self.misses, self.hit_rate() * 100.0, self.entries, self.capacity
+        )
+    }
+}
+
+type Spans = Arc<[(usize, usize)]>;
+type Shard = Mutex<HashMap<Box<str>, Spans>>;
+
+/// A bounded, thread-safe map from chunk text to token byte spans (relative to the chunk).
+///
+/// The capacity is split between up to `MAX_SHARDS` shards (fewer for small capacities), so the
+/// shards hold exactly `capacity` chunks between them. A shard is emptied when it is full, which
+/// keeps memory bounded without per-lookup bookkeeping; frequent chunks come back quickly.
+pub(crate) struct ChunkCache {
+    /// Each shard with its capacity.
+    shards: Vec<(Shard, usize)>,
+    hasher: RandomState,
+    capacity: usize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ChunkCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        let num_shards = capacity.min(MAX_SHARDS);
+        let shard_capacity = |i| capacity / num_shards + usize::from(i < capacity % num_shards);
+        ChunkCache {
+            shards: (0..num_shards).map(|i| (Mutex::new(HashMap::new()), shard_capacity(i))).collect(),
+            hasher: RandomState::new(),
+            capacity,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn shard(&self, chunk: &str) -> &(Shard, usize) {
+        &self.shards[self.hasher.hash_one(chunk) as usize % self.shards.len()]
+    }
+
+    /// The cached spans of `chunk`, counting a hit or a miss.
+    pub(crate) fn get(&self, chunk: &str) -> Option<Spans> {
+        let found = self.shard(chunk).0.lock().unwrap_or_else(|e| e.into_inner()).get(chunk).cloned();
+        let counter = if found.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        found
+    }
+
+    pub(crate) fn insert(&self, chunk: &str, spans: &[(usize, usize)]) {
+        let (shard, shard_capacity) = self.shard(chunk);
+        let mut shard = shard.lock().unwrap_or_else(|e| e.into_inner());
+        if shard.len() >= *shard_capacity {
+            shard.clear();
+        }
+        shard.insert(chunk.into(), spans.into());
+    }
+
+    pub(crate) fn clear(&self) {
+        for (shard, _) in &self.shards {
+            shard.lock().unwrap_or_else(|e| e.into_inner()).clear();
+        }
+    }
+
+    pub(crate) fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.shards.iter().map(|(s, _)| s.lock().unwrap_or_else(|e| e.into_inner()).len()).sum(),
+            capacity: self.capacity(),
+        }
+    }
+}
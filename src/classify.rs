@@ -0,0 +1,408 @@
+// src/classify.rs
+//! Decides which matching engine each rule pattern needs.
+//!
+//! Every pattern is parsed with `regex-syntax`. Patterns that denote a fixed set of strings
+//! are matched with Aho-Corasick, other patterns it accepts use the `regex` crate, and only
+//! patterns `regex-syntax` rejects (look-around, backreferences, other `fancy_regex` syntax)
+//! are compiled with `fancy_regex`. Where such a pattern is just a `regex` core wrapped in
+//! look-around (`(?<=[0-9])\+`, `(?<=[a-z]),(?=[a-z])`), the core is used as a prefilter.
+
+use std::fmt;
+
+use aho_corasick::{AhoCorasick, MatchKind, StartKind};
+use fancy_regex::Regex as FancyRegex;
+use regex_syntax::ast::ErrorKind as AstErrorKind;
+use regex_syntax::hir::literal::Extractor;
+
+use crate::error::{RuleKind, TokenizerError};
+
+/// The engine a pattern was routed to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PatternClass {
+    /// A fixed set of strings, matched with Aho-Corasick.
+    Literal,
+    /// No look-around or backreferences: `regex::Regex`.
+    Regex,
+    /// Needs `fancy_regex`.
+    Fancy,
+}
+
+impl PatternClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PatternClass::Literal => "literal",
+            PatternClass::Regex => "regex",
+            PatternClass::Fancy => "fancy",
+        }
+    }
+}
+
+impl fmt::Display for PatternClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// How one rule pattern was classified.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PatternClassification {
+    pub kind: RuleKind,
+    /// Index of the pattern within its rule list (0 for token_match / url_match).
+    pub index: usize,
+    pub pattern: String,
+    pub class: PatternClass,
+    /// Why the pattern needs `fancy_regex` (set for `PatternClass::Fancy` only).
+    pub reason: Option<String>,
+}
+
+/// The classification of every pattern of a `TokenizerRules`, in rule order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ClassificationReport {
+    pub patterns: Vec<PatternClassification>,
+}
+
+impl ClassificationReport {
+    /// Number of `kind` patterns routed to `class`.
+    pub fn count(&self, kind: RuleKind, class: PatternClass) -> usize {
+        self.patterns.iter().filter(|p| p.kind == kind && p.class == class).count()
+    }
+
+    /// The patterns that need `fancy_regex`.
+    pub fn fancy(&self) -> impl Iterator<Item = &PatternClassification> {
+        self.patterns.iter().filter(|p| p.class == PatternClass::Fancy)
+    }
+}
+
+impl fmt::Display for ClassificationReport {
+    /// One summary line per rule kind, then every `fancy_regex` pattern with its reason.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kinds = [RuleKind::Prefix, RuleKind::Suffix, RuleKind::Infix, RuleKind::TokenMatch, RuleKind::UrlMatch];
+        for kind in kinds {
+            writeln!(
+                f,
+                "{}: {} literal, {} regex, {} fancy",
+                kind,
+                self.count(kind, PatternClass::Literal),
+                self.count(kind, PatternClass::Regex),
+                self.count(kind, PatternClass::Fancy),
+            )?;
+        }
+        for p in self.fancy() {
+            writeln!(f, "  fancy {} #{} ('{}'): {}", p.kind, p.index, p.pattern, p.reason.as_deref().unwrap_or("-"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Where a pattern goes, with what that engine needs.
+#[derive(Clone)]
+pub(crate) enum Route {
+    /// The pattern matches exactly these strings, in preference order.
+    Literal(Vec<String>),
+    Regex,
+    Fancy { reason: String },
+}
+
+impl Route {
+    pub(crate) fn class(&self) -> PatternClass {
+        match self {
+            Route::Literal(_) => PatternClass::Literal,
+            Route::Regex => PatternClass::Regex,
+            Route::Fancy { .. } => PatternClass::Fancy,
+        }
+    }
+
+    fn reason(&self) -> Option<String> {
+        match self {
+            Route::Fancy { reason } => Some(reason.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// Size limit of the `regex` programs inside a compiled `fancy_regex`. It is above `regex`'s
+/// default, so a pattern `regex` refuses as too big can still fall back to `fancy_regex`.
+const FANCY_SIZE_LIMIT: usize = 64 << 20;
+
+/// Compiles `pattern` with `fancy_regex`, as every rule pattern that needs it is.
+pub(crate) fn fancy_regex(pattern: &str) -> Result<FancyRegex, Box<fancy_regex::Error>> {
+    fancy_regex::RegexBuilder::new(pattern).delegate_size_limit(FANCY_SIZE_LIMIT).build().map_err(Box::new)
+}
+
+/// Classifies `pattern` with `regex-syntax`.
+pub(crate) fn route(pattern: &str) -> Route {
+    let hir = match regex_syntax::ast::parse::Parser::new().parse(pattern) {
+        Err(e) => {
+            let reason = match e.kind() {
+                AstErrorKind::UnsupportedLookAround => "look-around".to_string(),
+                AstErrorKind::UnsupportedBackreference => "backreference".to_string(),
+                other => other.to_string(),
+            };
+            return Route::Fancy { reason };
+        }
+        Ok(ast) => match regex_syntax::hir::translate::Translator::new().translate(pattern, &ast) {
+            Ok(hir) => hir,
+            Err(e) => return Route::Fancy { reason: e.kind().to_string() },
+        },
+    };
+    // Exact literals with no look-around assertions (`^`, `$`, `\b`) search exactly like the regex.
+    if hir.properties().look_set().is_empty() {
+        let seq = Extractor::new().extract(&hir);
+        if let (true, Some(literals)) = (seq.is_exact(), seq.literals()) {
+            let strings: Option<Vec<String>> = literals.iter()
+                .map(|lit| String::from_utf8(lit.as_bytes().to_vec()).ok().filter(|s| !s.is_empty()))
+                .collect();
+            if let Some(strings) = strings.filter(|s| !s.is_empty()) {
+                return Route::Literal(strings);
+            }
+        }
+    }
+    Route::Regex
+}
+
+/// Classifies `pattern` for the report.
+pub(crate) fn classification(kind: RuleKind, index: usize, pattern: &str, route: &Route) -> PatternClassification {
+    PatternClassification { kind, index, pattern: pattern.to_string(), class: route.class(), reason: route.reason() }
+}
+
+/// A pattern split into a leading look-behind, a core and a trailing look-ahead.
+pub(crate) struct LookaroundParts<'p> {
+    /// Body of a leading positive look-behind `(?<=...)`.
+    pub(crate) lookbehind: Option<&'p str>,
+    pub(crate) core: &'p str,
+    /// Body of a trailing positive look-ahead `(?=...)`.
+    pub(crate) lookahead: Option<&'p str>,
+}
+
+/// Strips a leading look-behind and a trailing look-ahead (positive or negative) off `pattern`.
+/// Only the bodies of positive ones are kept; the core is the rest, possibly empty.
+pub(crate) fn split_lookaround(pattern: &str) -> Option<LookaroundParts<'_>> {
+    let mut parts = LookaroundParts { lookbehind: None, core: pattern, lookahead: None };
+    if pattern.starts_with("(?<=") || pattern.starts_with("(?<!") {
+        let end = group_end(pattern)?;
+        if pattern.starts_with("(?<=") {
+            parts.lookbehind = Some(&pattern["(?<=".len()..end - 1]);
+        }
+        parts.core = &pattern[end..];
+    }
+    if let Some(start) = trailing_lookahead_start(parts.core) {
+        if parts.core[start..].starts_with("(?=") {
+            parts.lookahead = Some(&parts.core[start + "(?=".len()..parts.core.len() - 1]);
+        }
+        parts.core = &parts.core[..start];
+    }
+    Some(parts)
+}
+
+/// For a pattern that is a `regex`-compatible core wrapped in a leading look-behind and/or a
+/// trailing look-ahead, returns the core. The core matches wherever the pattern does.
+pub(crate) fn lookaround_core(pattern: &str) -> Option<&str> {
+    let core = split_lookaround(pattern)?.core;
+    (core.len() < pattern.len() && !core.is_empty() && regex_syntax::parse(core).is_ok()).then_some(core)
+}
+
+/// Byte index just past the group that opens at the start of `pattern`.
+fn group_end(pattern: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut in_class = false;
+    let mut chars = pattern.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => { chars.next(); }
+            '[' if !in_class => in_class = true,
+            ']' if in_class => in_class = false,
+            '(' if !in_class => depth += 1,
+            ')' if !in_class => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Whether `pattern` has a `|` outside any group or class, so a leading or trailing
+/// look-around only applies to the first or last alternative.
+pub(crate) fn has_top_level_alternation(pattern: &str) -> bool {
+    let mut depth = 0;
+    let mut in_class = false;
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => { chars.next(); }
+            '[' if !in_class => in_class = true,
+            ']' if in_class => in_class = false,
+            '(' if !in_class => depth += 1,
+            ')' if !in_class => depth -= 1,
+            '|' if !in_class && depth == 0 => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Byte index of a top-level `(?=...)`/`(?!...)` group that closes `pattern`, if any.
+fn trailing_lookahead_start(pattern: &str) -> Option<usize> {
+    let mut start = 0;
+    while let Some(offset) = pattern[start..].find("(?=").or_else(|| pattern[start..].find("(?!")) {
+        let candidate = start + offset;
+        if group_end(&pattern[candidate..]).map(|end| candidate + end) == Some(pattern.len()) {
+            return Some(candidate);
+        }
+        start = candidate + 1;
+    }
+    None
+}
+
+/// A compiled pattern routed to `regex` or `fancy_regex`.
+#[derive(Clone)]
+pub(crate) enum RuleRegex {
+    Fast(regex::Regex),
+    Fancy {
+        re: FancyRegex,
+        /// The look-around core (see `lookaround_core`); no core match means no match.
+        prefilter: Option<regex::Regex>,
+    },
+}
+
+impl RuleRegex {
+    /// Compiles `pattern`, the `index`-th of its rule list, for `route` (`Route::Literal`
+    /// patterns compile with `regex`).
+    /// Falls back to `fancy_regex` (with a larger size limit, see `fancy_regex`) if the `regex`
+    /// crate refuses the pattern, and then turns `route` into `Route::Fancy`, so reports show
+    /// the engine actually used.
+    pub(crate) fn new(kind: RuleKind, index: usize, pattern: &str, route: &mut Route) -> Result<Self, TokenizerError> {
+        if !matches!(route, Route::Fancy { .. }) {
+            match regex::Regex::new(pattern) {
+                Ok(re) => return Ok(RuleRegex::Fast(re)),
+                Err(e) => *route = Route::Fancy { reason: format!("rejected by regex: {}", e) },
+            }
+        }
+        let re = fancy_regex(pattern).map_err(|e| TokenizerError::InvalidPattern {
+            kind,
+            index,
+            pattern: pattern.to_string(),
+            source: e,
+        })?;
+        let prefilter = lookaround_core(pattern).and_then(|core| regex::Regex::new(core).ok());
+        Ok(RuleRegex::Fancy { re, prefilter })
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        match self {
+            RuleRegex::Fast(re) => re.as_str(),
+            RuleRegex::Fancy { re, .. } => re.as_str(),
+        }
+    }
+
+    /// Leftmost match starting at or after `start` (look-behind may look before `start`).
+    pub(crate) fn find_at(&self, text: &str, start: usize) -> Result<Option<(usize, usize)>, Box<fancy_regex::Error>> {
+        match self {
+            RuleRegex::Fast(re) => Ok(re.find_at(text, start).map(|m| (m.start(), m.end()))),
+            RuleRegex::Fancy { re, prefilter } => {
+                // A fancy match starts where its core matches, so the search can begin there.
+                let start = match prefilter {
+                    Some(prefilter) => match prefilter.find_at(text, start) {
+                        Some(m) => m.start(),
+                        None => return Ok(None),
+                    },
+                    None => start,
+                };
+                Ok(re.find_from_pos(text, start).map_err(Box::new)?.map(|m| (m.start(), m.end())))
+            }
+        }
+    }
+
+    /// All non-overlapping matches, as `find_iter` of the underlying engine.
+    pub(crate) fn find_all(&self, text: &str) -> Result<Vec<(usize, usize)>, Box<fancy_regex::Error>> {
+        match self {
+            RuleRegex::Fast(re) => Ok(re.find_iter(text).map(|m| (m.start(), m.end())).collect()),
+            RuleRegex::Fancy { re, prefilter } => {
+                if prefilter.as_ref().is_some_and(|p| !p.is_match(text)) {
+                    return Ok(Vec::new());
+                }
+                re.find_iter(text).map(|m| m.map(|m| (m.start(), m.end())).map_err(Box::new)).collect()
+            }
+        }
+    }
+}
+
+/// Literal patterns (`Route::Literal`) searched together with one Aho-Corasick automaton.
+#[derive(Clone)]
+pub(crate) struct LiteralSet {
+    matcher: AhoCorasick,
+    /// Rule index of each automaton pattern.
+    pattern_of: Vec<usize>,
+}
+
+impl LiteralSet {
+    /// `rules` are `(rule index, strings in preference order)`. With `reversed`, the strings are
+    /// stored back to front for end-anchored searches over reversed text.
+    fn build(rules: &[(usize, Vec<String>)], match_kind: MatchKind, start_kind: StartKind, reversed: bool) -> Result<Self, TokenizerError> {
+        let mut strings: Vec<Vec<u8>> = Vec::new();
+        let mut pattern_of = Vec::new();
+        for (index, rule_strings) in rules {
+            for s in rule_strings {
+                let mut bytes = s.as_bytes().to_vec();
+                if reversed {
+                    bytes.reverse();
+                }
+                strings.push(bytes);
+                pattern_of.push(*index);
+            }
+        }
+        let matcher = AhoCorasick::builder().match_kind(match_kind).start_kind(start_kind).build(&strings)?;
+        Ok(LiteralSet { matcher, pattern_of })
+    }
+
+    /// For prefix runs: the first (in rule order) literal that `text` starts with.
+    pub(crate) fn prefix(rules: &[(usize, Vec<String>)]) -> Result<Self, TokenizerError> {
+        Self::build(rules, MatchKind::LeftmostFirst, StartKind::Anchored, false)
+    }
+
+    /// For suffix runs: the longest literal that `text` ends with.
+    pub(crate) fn suffix(rules: &[(usize, Vec<String>)]) -> Result<Self, TokenizerError> {
+        Self::build(rules, MatchKind::LeftmostLongest, StartKind::Anchored, true)
+    }
+
+    /// For infixes: every literal rule matched as its own `find_iter` would.
+    pub(crate) fn infix(rules: &[(usize, Vec<String>)]) -> Result<Self, TokenizerError> {
+        Self::build(rules, MatchKind::Standard, StartKind::Unanchored, false)
+    }
+
+    /// `(end, rule index)` of the prefix of `text`, see `LiteralSet::prefix`.
+    pub(crate) fn find_prefix(&self, text: &str) -> Option<(usize, usize)> {
+        let input = aho_corasick::Input::new(text).anchored(aho_corasick::Anchored::Yes);
+        self.matcher.find(input).map(|m| (m.end(), self.pattern_of[m.pattern().as_usize()]))
+    }
+
+    /// `(start, rule index)` of the suffix of `text`, see `LiteralSet::suffix`.
+    pub(crate) fn find_suffix(&self, text: &str) -> Option<(usize, usize)> {
+        let reversed: Vec<u8> = text.bytes().rev().collect();
+        let input = aho_corasick::Input::new(&reversed).anchored(aho_corasick::Anchored::Yes);
+        self.matcher.find(input).map(|m| (text.len() - m.end(), self.pattern_of[m.pattern().as_usize()]))
+    }
+
+    /// `(start, end, rule index)` of every match, per rule exactly the non-overlapping
+    /// leftmost-first matches its own regex would find.
+    pub(crate) fn find_all(&self, text: &str) -> Vec<(usize, usize, usize)> {
+        // All occurrences, ordered by start and then by preference, then a greedy pass per rule.
+        let mut occurrences: Vec<(usize, usize, usize)> = self.matcher.find_overlapping_iter(text)
+            .map(|m| (m.start(), m.pattern().as_usize(), m.end()))
+            .collect();
+        occurrences.sort_unstable();
+        let mut next_free = vec![0; self.pattern_of.iter().max().map_or(0, |m| m + 1)];
+        let mut matches = Vec::new();
+        for (start, pattern, end) in occurrences {
+            let rule = self.pattern_of[pattern];
+            if start >= next_free[rule] {
+                next_free[rule] = end;
+                matches.push((start, end, rule));
+            }
+        }
+        matches
+    }
+}
@@ -0,0 +1,121 @@
+// src/config.rs
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::TokenizerError;
+use crate::pattern::{self, ExceptionMap};
+use crate::tokenizer::TokenizerRules;
+
+/// One token of a special case, as attribute name -> value (e.g. `ORTH`, `NORM`).
+pub type SpecialCaseToken = BTreeMap<String, String>;
+
+/// Declarative, serializable description of a tokenizer rule set.
+///
+/// Field names follow spaCy's tokenizer settings. Every field is optional in the file;
+/// missing lists are empty and missing matchers are disabled. Literal infixes are matched
+/// leftmost-first in the order given, so list longer strings before their prefixes.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RulesConfig {
+    pub prefixes: Vec<String>,
+    pub suffixes: Vec<String>,
+    pub infixes: Vec<String>,
+    pub literal_infixes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_match: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url_match: Option<String>,
+    pub special_cases: BTreeMap<String, Vec<SpecialCaseToken>>,
+}
+
+/// Serialization format of a rules file, chosen from its extension.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+}
+
+impl ConfigFormat {
+    pub fn from_path(path: &Path) -> Result<Self, TokenizerError> {
+        match path.extension().and_then(|e| e.to_str()).map(|e| e.to_ascii_lowercase()).as_deref() {
+            Some("json") => Ok(ConfigFormat::Json),
+            Some("toml") => Ok(ConfigFormat::Toml),
+            _ => Err(TokenizerError::InvalidConfig(format!(
+                "cannot infer rules format of '{}': expected a .json or .toml extension",
+                path.display()
+            ))),
+        }
+    }
+}
+
+impl RulesConfig {
+    /// The built-in English rule set from `pattern.rs`.
+    pub fn english() -> Self {
+        RulesConfig {
+            prefixes: pattern::get_english_prefix_patterns().into_iter().map(String::from).collect(),
+            suffixes: pattern::get_english_suffix_patterns(),
+            infixes: pattern::get_english_regex_infix_patterns(),
+            literal_infixes: pattern::get_english_literal_infix_strings(),
+            token_match: pattern::get_english_token_match_pattern_str(),
+            url_match: Some(pattern::get_english_url_match_pattern_str()),
+            special_cases: pattern::get_english_tokenizer_exceptions()
+                .into_iter()
+                .map(|(key, tokens)| (key, tokens.into_iter().map(|attrs| attrs.into_iter().collect()).collect()))
+                .collect(),
+        }
+    }
+
+    /// Reads a rules file, picking JSON or TOML from the file extension.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, TokenizerError> {
+        let path = path.as_ref();
+        let format = ConfigFormat::from_path(path)?;
+        let content = fs::read_to_string(path)?;
+        Self::from_str_with_format(&content, format)
+    }
+
+    pub fn from_str_with_format(content: &str, format: ConfigFormat) -> Result<Self, TokenizerError> {
+        match format {
+            ConfigFormat::Json => serde_json::from_str(content).map_err(|e| TokenizerError::InvalidConfig(e.to_string())),
+            ConfigFormat::Toml => toml::from_str(content).map_err(|e| TokenizerError::InvalidConfig(e.to_string())),
+        }
+    }
+
+    pub fn to_string_with_format(&self, format: ConfigFormat) -> Result<String, TokenizerError> {
+        match format {
+            ConfigFormat::Json => serde_json::to_string_pretty(self).map_err(|e| TokenizerError::InvalidConfig(e.to_string())),
+            ConfigFormat::Toml => toml::to_string_pretty(self).map_err(|e| TokenizerError::InvalidConfig(e.to_string())),
+        }
+    }
+
+    /// Writes the rules to `path`, picking JSON or TOML from the file extension.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), TokenizerError> {
+        let path = path.as_ref();
+        let content = self.to_string_with_format(ConfigFormat::from_path(path)?)?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Compiles the patterns into a `TokenizerRules`.
+    pub fn compile(&self) -> Result<TokenizerRules, TokenizerError> {
+        let exceptions: ExceptionMap = self
+            .special_cases
+            .iter()
+            .map(|(key, tokens)| {
+                let tokens = tokens.iter().map(|attrs| attrs.iter().map(|(k, v)| (k.clone(), v.clone())).collect::<HashMap<_, _>>()).collect();
+                (key.clone(), tokens)
+            })
+            .collect();
+        TokenizerRules::from_patterns(
+            &self.prefixes,
+            &self.suffixes,
+            &self.infixes,
+            &self.literal_infixes,
+            self.token_match.as_deref(),
+            self.url_match.as_deref(),
+            exceptions,
+        )
+    }
+}
@@ -0,0 +1,130 @@
+// src/doc.rs
+use std::borrow::Cow;
+use std::ops::Index;
+
+use crate::error::RuleError;
+use crate::lex_attrs::AttrValue;
+use crate::strings::StringStore;
+use crate::symbols::Symbol;
+use crate::token::Token;
+
+/// A tokenized document, in the spirit of spaCy's `Doc`.
+///
+/// Owns the original text and the full token list, including the whitespace tokens the
+/// tokenizer emits for extra spaces, tabs and newlines. Every byte of the input is covered
+/// either by a token or by a token's trailing space, so `text_with_ws()` reproduces it exactly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Doc {
+    text: String,
+    tokens: Vec<Token>,
+    /// The `NORM` set by a special case on each token, if any.
+    norms: Vec<Option<String>>,
+    rule_errors: Vec<RuleError>,
+}
+
+impl Doc {
+    /// Built by the tokenizer only, so `tokens` always cover `text` losslessly.
+    pub(crate) fn new(text: String, tokens: Vec<Token>) -> Self {
+        let norms = vec![None; tokens.len()];
+        let doc = Doc { text, tokens, norms, rule_errors: Vec::new() };
+        debug_assert_eq!(doc.text_with_ws(), doc.text, "tokens do not cover the document text losslessly");
+        doc
+    }
+
+    pub(crate) fn with_norms(mut self, norms: Vec<Option<String>>) -> Self {
+        debug_assert_eq!(norms.len(), self.tokens.len());
+        self.norms = norms;
+        self
+    }
+
+    pub(crate) fn with_rule_errors(mut self, rule_errors: Vec<RuleError>) -> Self {
+        self.rule_errors = rule_errors;
+        self
+    }
+
+    /// The original input text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Rebuilds the text from the tokens and their trailing whitespace, as spaCy's `Doc.text`.
+    pub fn text_with_ws(&self) -> String {
+        let mut out = String::with_capacity(self.text.len());
+        for token in &self.tokens {
+            out.push_str(&token.text);
+            out.push_str(token.whitespace());
+        }
+        out
+    }
+
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Token> {
+        self.tokens.iter()
+    }
+
+    /// The norm of token `i`, as spaCy's `token.norm_`: the `NORM` of the special case that
+    /// produced it, or else its lowercase form.
+    pub fn norm(&self, i: usize) -> Cow<'_, str> {
+        match &self.norms[i] {
+            Some(norm) => Cow::Borrowed(norm),
+            None => Cow::Owned(self.tokens[i].text.to_lowercase()),
+        }
+    }
+
+    /// Rules that failed at match time while tokenizing this document, and on which chunks.
+    pub fn rule_errors(&self) -> &[RuleError] {
+        &self.rule_errors
+    }
+
+    /// The values of `attrs` for each token, as spaCy's `Doc.to_array`: one row per token, one
+    /// column per attribute. Flags are 0 or 1, `LENGTH` and `IDX` (the char offset) are numbers,
+    /// `SPACY` is the trailing space flag, and strings (including `NORM`, see `norm`) are ids
+    /// interned in `strings`. Attributes the tokenizer does not set (`POS`, `DEP`, ...) are 0.
+    pub fn to_array(&self, attrs: &[Symbol], strings: &mut StringStore) -> Vec<Vec<u64>> {
+        self.tokens.iter().enumerate()
+            .map(|(i, token)| attrs.iter().map(|&name| match name {
+                Symbol::SPACY => u64::from(token.whitespace_after),
+                Symbol::IDX => token.start_char as u64,
+                Symbol::NORM => strings.add(&self.norm(i)),
+                _ => match token.get(name) {
+                    Some(AttrValue::Bool(flag)) => u64::from(flag),
+                    Some(AttrValue::Int(n)) => n as u64,
+                    Some(AttrValue::Str(s)) => strings.add(&s),
+                    None => 0,
+                },
+            }).collect())
+            .collect()
+    }
+
+    pub fn into_tokens(self) -> Vec<Token> {
+        self.tokens
+    }
+}
+
+impl Index<usize> for Doc {
+    type Output = Token;
+
+    fn index(&self, i: usize) -> &Token {
+        &self.tokens[i]
+    }
+}
+
+impl<'a> IntoIterator for &'a Doc {
+    type Item = &'a Token;
+    type IntoIter = std::slice::Iter<'a, Token>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tokens.iter()
+    }
+}
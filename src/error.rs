@@ -0,0 +1,139 @@
+// src/error.rs
+use std::fmt;
+
+/// Which kind of tokenizer rule a pattern belongs to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RuleKind {
+    Prefix,
+    Suffix,
+    Infix,
+    TokenMatch,
+    UrlMatch,
+}
+
+impl RuleKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleKind::Prefix => "prefix",
+            RuleKind::Suffix => "suffix",
+            RuleKind::Infix => "infix",
+            RuleKind::TokenMatch => "token_match",
+            RuleKind::UrlMatch => "url_match",
+        }
+    }
+}
+
+impl fmt::Display for RuleKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Errors raised while building or loading tokenizer rules.
+#[derive(Debug)]
+pub enum TokenizerError {
+    /// A rule pattern failed to compile.
+    InvalidPattern {
+        kind: RuleKind,
+        /// Index of the pattern within its rule list (0 for `token_match` and `url_match`).
+        index: usize,
+        pattern: String,
+        source: Box<fancy_regex::Error>,
+    },
+    /// The Aho-Corasick matcher for literal infixes could not be built.
+    MatcherBuild(aho_corasick::BuildError),
+    /// A rule failed at match time under `RegexErrorPolicy::Fail`.
+    RegexRuntime(RuleError),
+    /// A special case was rejected by `validate_special_case`.
+    InvalidSpecialCase { key: String, reason: String },
+    /// A rules file could not be parsed or serialized.
+    InvalidConfig(String),
+    /// Reading rule data from disk failed.
+    Io(std::io::Error),
+    /// The tokenizer's thread pool could not be built.
+    ThreadPool(rayon::ThreadPoolBuildError),
+}
+
+impl fmt::Display for TokenizerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenizerError::InvalidPattern { kind, index, pattern, source } => {
+                write!(f, "invalid {} pattern #{} '{}': {}", kind, index, pattern, source)
+            }
+            TokenizerError::MatcherBuild(e) => write!(f, "failed to build literal infix matcher: {}", e),
+            TokenizerError::RegexRuntime(e) => write!(f, "regex runtime error: {}", e),
+            TokenizerError::InvalidSpecialCase { key, reason } => write!(f, "invalid special case '{}': {}", key, reason),
+            TokenizerError::InvalidConfig(msg) => write!(f, "invalid rules config: {}", msg),
+            TokenizerError::Io(e) => write!(f, "I/O error: {}", e),
+            TokenizerError::ThreadPool(e) => write!(f, "failed to build thread pool: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for TokenizerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TokenizerError::InvalidPattern { source, .. } => Some(source.as_ref()),
+            TokenizerError::MatcherBuild(e) => Some(e),
+            TokenizerError::RegexRuntime(_) | TokenizerError::InvalidSpecialCase { .. } | TokenizerError::InvalidConfig(_) => None,
+            TokenizerError::Io(e) => Some(e),
+            TokenizerError::ThreadPool(e) => Some(e),
+        }
+    }
+}
+
+impl From<aho_corasick::BuildError> for TokenizerError {
+    fn from(e: aho_corasick::BuildError) -> Self {
+        TokenizerError::MatcherBuild(e)
+    }
+}
+
+impl From<rayon::ThreadPoolBuildError> for TokenizerError {
+    fn from(e: rayon::ThreadPoolBuildError) -> Self {
+        TokenizerError::ThreadPool(e)
+    }
+}
+
+impl From<std::io::Error> for TokenizerError {
+    fn from(e: std::io::Error) -> Self {
+        TokenizerError::Io(e)
+    }
+}
+
+/// What to do when a compiled rule fails at match time (e.g. fancy-regex hits its backtrack limit).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RegexErrorPolicy {
+    /// Abort tokenization with `TokenizerError::RegexRuntime`.
+    Fail,
+    /// Send a warning to the trace sink (`TraceSink::rule_error`), skip the rule for that chunk
+    /// and record it in the report.
+    WarnAndFallback,
+    /// Silently skip the rule for that chunk and record it in the report.
+    #[default]
+    Count,
+}
+
+/// A rule that failed at match time on a specific chunk.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RuleError {
+    pub kind: RuleKind,
+    /// Index of the pattern within its rule list (always 0 for token_match / url_match).
+    /// Prefixes and suffixes are matched as combined runs of patterns; for them this is the
+    /// index of the run's first pattern and `pattern` is the combined alternation.
+    pub pattern_index: usize,
+    pub pattern: String,
+    pub chunk: String,
+    /// Char offset of the chunk within the tokenized text.
+    pub chunk_start_char: usize,
+    pub message: String,
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} pattern #{} ('{}') failed on chunk '{}' at char {}: {}",
+            self.kind, self.pattern_index, self.pattern, self.chunk, self.chunk_start_char, self.message
+        )
+    }
+}
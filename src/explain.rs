@@ -0,0 +1,63 @@
+// src/explain.rs
+use std::borrow::Cow;
+use std::fmt;
+
+/// The rule that produced a token, as reported by `Tokenizer::explain`.
+///
+/// Mirrors the labels of spaCy's `nlp.tokenizer.explain()` and additionally keeps
+/// the index of the pattern that matched within its rule list.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TokenRule {
+    /// The n-th token (1-based) of a special case.
+    Special(usize),
+    TokenMatch,
+    UrlMatch,
+    /// Index into the prefix patterns.
+    Prefix(usize),
+    /// Index into the suffix patterns.
+    Suffix(usize),
+    /// Index into the regex infix patterns.
+    Infix(usize),
+    /// Index into the literal infix strings.
+    LiteralInfix(usize),
+    /// Text between affixes and infixes, or a chunk that no rule split.
+    Token,
+}
+
+impl TokenRule {
+    /// The spaCy label: `SPECIAL-1`, `TOKEN_MATCH`, `URL_MATCH`, `PREFIX`, `SUFFIX`, `INFIX` or `TOKEN`.
+    pub fn name(&self) -> Cow<'static, str> {
+        match self {
+            TokenRule::Special(n) => Cow::Owned(format!("SPECIAL-{}", n)),
+            TokenRule::TokenMatch => Cow::Borrowed("TOKEN_MATCH"),
+            TokenRule::UrlMatch => Cow::Borrowed("URL_MATCH"),
+            TokenRule::Prefix(_) => Cow::Borrowed("PREFIX"),
+            TokenRule::Suffix(_) => Cow::Borrowed("SUFFIX"),
+            TokenRule::Infix(_) | TokenRule::LiteralInfix(_) => Cow::Borrowed("INFIX"),
+            TokenRule::Token => Cow::Borrowed("TOKEN"),
+        }
+    }
+
+    pub fn pattern_index(&self) -> Option<usize> {
+        match self {
+            TokenRule::Prefix(i) | TokenRule::Suffix(i) | TokenRule::Infix(i) | TokenRule::LiteralInfix(i) => Some(*i),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TokenRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.pattern_index() {
+            Some(i) => write!(f, "{}#{}", self.name(), i),
+            None => f.write_str(&self.name()),
+        }
+    }
+}
+
+/// One `(rule, token)` pair from `Tokenizer::explain`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Explanation {
+    pub rule: TokenRule,
+    pub text: String,
+}
@@ -0,0 +1,163 @@
+// src/fastpath.rs
+//! Shortcut for chunks no rule can split.
+//!
+//! Most chunks are plain runs of letters ("the", "Straße", "東京"). A prefix, suffix or infix
+//! can only split such a run if one of its patterns has a non-empty match made only of letters.
+//! Walking each pattern's `regex-syntax` HIR once per rule set gives the letters such a match
+//! can start with (the emoticon `xD` → `x`, `X`), while patterns whose matches always contain a
+//! non-letter (`\.`, `'s`, `(?<=[0-9])km`) contribute nothing. A run of letters containing none
+//! of those starting letters cannot be split, so after the special-case lookup it skips the
+//! rules. A pattern that cannot be analyzed disables the shortcut. `token_match` and
+//! `url_match` are irrelevant here: they can only keep a chunk whole.
+
+use std::cmp::Ordering;
+
+use regex_syntax::hir::{Class, ClassUnicode, ClassUnicodeRange, Hir, HirKind};
+
+use crate::classify;
+
+/// Recognizes chunks made only of letters (the Unicode `Alphabetic` property) that no rule can split.
+#[derive(Clone, Debug)]
+pub(crate) struct PlainChunks {
+    /// Letters that cannot start a letter-only rule match (from the same `regex-syntax`
+    /// tables the patterns are checked against).
+    safe: ClassUnicode,
+    /// `safe`, for ASCII.
+    safe_ascii: [bool; 128],
+}
+
+impl PlainChunks {
+    /// Analyzes the prefix, suffix and regex infix `patterns` and the literal infixes.
+    /// Returns `None` if a pattern cannot be analyzed.
+    pub(crate) fn for_rules<'p>(patterns: impl IntoIterator<Item = &'p str>, literal_infixes: &[String]) -> Option<Self> {
+        let letters = letter_class();
+        let mut risky = ClassUnicode::empty();
+        for literal in literal_infixes {
+            if let Some(first) = literal.chars().next().filter(|_| literal.chars().all(|c| contains(&letters, c))) {
+                risky.push(ClassUnicodeRange::new(first, first));
+            }
+        }
+        for pattern in patterns {
+            risky.union(&pattern_first_letters(pattern, &letters)?);
+        }
+        let mut safe = letters;
+        safe.difference(&risky);
+        let mut safe_ascii = [false; 128];
+        for (b, slot) in safe_ascii.iter_mut().enumerate() {
+            *slot = contains(&safe, b as u8 as char);
+        }
+        Some(PlainChunks { safe, safe_ascii })
+    }
+
+    /// Whether `chunk` is a non-empty run of letters that no rule can split.
+    pub(crate) fn is_plain(&self, chunk: &str) -> bool {
+        !chunk.is_empty() && chunk.chars().all(|c| match self.safe_ascii.get(c as usize) {
+            Some(&safe) => safe,
+            None => contains(&self.safe, c),
+        })
+    }
+}
+
+fn contains(class: &ClassUnicode, c: char) -> bool {
+    class.ranges().binary_search_by(|range| {
+        if range.end() < c {
+            Ordering::Less
+        } else if range.start() > c {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }).is_ok()
+}
+
+fn letter_class() -> ClassUnicode {
+    match regex_syntax::parse(r"\p{Alphabetic}").map(|hir| hir.into_kind()) {
+        Ok(HirKind::Class(Class::Unicode(class))) => class,
+        _ => unreachable!("\\p{{Alphabetic}} is a Unicode class"),
+    }
+}
+
+/// The letters a non-empty, letters-only match of `pattern` can start with,
+/// or `None` if the pattern cannot be analyzed.
+fn pattern_first_letters(pattern: &str, letters: &ClassUnicode) -> Option<ClassUnicode> {
+    if let Ok(hir) = regex_syntax::parse(pattern) {
+        return Some(first_letters(&hir, letters));
+    }
+    // `fancy_regex` syntax: only look-around wrapped around a `regex` core is understood. With a
+    // top-level `|`, the look-around only guards one alternative and the pattern is not such a wrap.
+    if classify::has_top_level_alternation(pattern) {
+        return None;
+    }
+    let parts = classify::split_lookaround(pattern)?;
+    // A positive look-around that must see a non-letter can never succeed inside a run of letters.
+    let guards = |body: Option<&str>| body.and_then(|b| regex_syntax::parse(b).ok())
+        .is_some_and(|hir| is_mandatory(&hir) && first_letters(&hir, letters).ranges().is_empty());
+    if guards(parts.lookbehind) || guards(parts.lookahead) {
+        return Some(ClassUnicode::empty());
+    }
+    // Look-around consumes nothing, so the match starts where the core's match does.
+    regex_syntax::parse(parts.core).ok().map(|hir| first_letters(&hir, letters))
+}
+
+/// Whether every match of `hir` is non-empty.
+fn is_mandatory(hir: &Hir) -> bool {
+    hir.properties().minimum_len().is_some_and(|len| len > 0)
+}
+
+/// Over-approximates the first chars of the non-empty matches of `hir` made only of `letters`.
+/// Empty when `hir` has no such match (e.g. every match contains a non-letter).
+fn first_letters(hir: &Hir, letters: &ClassUnicode) -> ClassUnicode {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => ClassUnicode::empty(),
+        HirKind::Literal(literal) => match std::str::from_utf8(&literal.0) {
+            Ok(s) if s.chars().all(|c| contains(letters, c)) => match s.chars().next() {
+                Some(first) => ClassUnicode::new([ClassUnicodeRange::new(first, first)]),
+                None => ClassUnicode::empty(),
+            },
+            Ok(_) => ClassUnicode::empty(),
+            // Not valid UTF-8 on its own (a `(?-u)` byte literal): assume any letter.
+            Err(_) => letters.clone(),
+        },
+        HirKind::Class(Class::Unicode(class)) => {
+            let mut class = class.clone();
+            class.intersect(letters);
+            class
+        }
+        HirKind::Class(Class::Bytes(class)) => match class.to_unicode_class() {
+            Some(mut class) => {
+                class.intersect(letters);
+                class
+            }
+            // A byte above ASCII could be part of any letter.
+            None => letters.clone(),
+        },
+        HirKind::Repetition(rep) if rep.max == Some(0) => ClassUnicode::empty(),
+        HirKind::Repetition(rep) => first_letters(&rep.sub, letters),
+        HirKind::Capture(capture) => first_letters(&capture.sub, letters),
+        // The match starts in the first part that matches something, which is at or before the
+        // first part that always does. A part that always matches but never letters-only rules
+        // out the whole concatenation.
+        HirKind::Concat(parts) => {
+            let mut first = ClassUnicode::empty();
+            let mut started = false;
+            for part in parts {
+                let part_first = first_letters(part, letters);
+                if is_mandatory(part) && part_first.ranges().is_empty() {
+                    return ClassUnicode::empty();
+                }
+                if !started {
+                    first.union(&part_first);
+                    started = is_mandatory(part);
+                }
+            }
+            first
+        }
+        HirKind::Alternation(branches) => {
+            let mut first = ClassUnicode::empty();
+            for branch in branches {
+                first.union(&first_letters(branch, letters));
+            }
+            first
+        }
+    }
+}
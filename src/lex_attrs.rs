@@ -0,0 +1,442 @@
+// src/lex_attrs.rs
+//! Lexical attributes of token text, as spaCy's `lang/lex_attrs.py`.
+//!
+//! Each attribute is keyed by its `Symbol` (`IS_PUNCT`, `LIKE_NUM`, `SHAPE`, ...) and computed
+//! from the token text alone. The getters follow spaCy's, including its use of Python's string
+//! predicates (`str.isalpha`, `str.isdigit`, `str.istitle`, ...), which are reproduced here over
+//! Unicode general categories. `LexAttrs` is the table of getters, so a language can override
+//! one (spaCy's `lex_attr_getters`), as `LexAttrs::english` does for `LIKE_NUM`; `Token::get`
+//! uses `LexAttrs::default_table`, the English one.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use fancy_regex::Regex as FancyRegex;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use unicode_general_category::{get_general_category, GeneralCategory};
+
+use crate::pattern;
+use crate::symbols::Symbol;
+
+/// The value of a lexical attribute.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AttrValue<'a> {
+    /// A flag such as `IS_PUNCT`.
+    Bool(bool),
+    /// A count such as `LENGTH`.
+    Int(usize),
+    /// A string such as `LOWER` or `SHAPE`.
+    Str(Cow<'a, str>),
+}
+
+impl<'a> AttrValue<'a> {
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            AttrValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<usize> {
+        match self {
+            AttrValue::Int(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AttrValue::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+/// Computes one lexical attribute from the token text.
+#[derive(Copy, Clone)]
+pub enum AttrGetter {
+    Flag(fn(&str) -> bool),
+    Int(fn(&str) -> usize),
+    Str(for<'a> fn(&'a str) -> Cow<'a, str>),
+}
+
+impl AttrGetter {
+    pub fn compute<'a>(&self, text: &'a str) -> AttrValue<'a> {
+        match self {
+            AttrGetter::Flag(getter) => AttrValue::Bool(getter(text)),
+            AttrGetter::Int(getter) => AttrValue::Int(getter(text)),
+            AttrGetter::Str(getter) => AttrValue::Str(getter(text)),
+        }
+    }
+}
+
+/// A table of lexical attribute getters keyed by `Symbol`, as spaCy's `lex_attr_getters`.
+#[derive(Clone)]
+pub struct LexAttrs {
+    getters: HashMap<Symbol, AttrGetter>,
+}
+
+static DEFAULT_TABLE: Lazy<LexAttrs> = Lazy::new(LexAttrs::english);
+
+impl LexAttrs {
+    /// spaCy's language-independent getters (`LEX_ATTRS`), plus `ORTH` and `LENGTH`.
+    pub fn new() -> Self {
+        let getters = [
+            (Symbol::ORTH, AttrGetter::Str(orth)),
+            (Symbol::LOWER, AttrGetter::Str(lower)),
+            (Symbol::PREFIX, AttrGetter::Str(prefix)),
+            (Symbol::SUFFIX, AttrGetter::Str(suffix)),
+            (Symbol::SHAPE, AttrGetter::Str(word_shape)),
+            (Symbol::LENGTH, AttrGetter::Int(length)),
+            (Symbol::IS_ALPHA, AttrGetter::Flag(is_alpha)),
+            (Symbol::IS_ASCII, AttrGetter::Flag(is_ascii)),
+            (Symbol::IS_DIGIT, AttrGetter::Flag(is_digit)),
+            (Symbol::IS_LOWER, AttrGetter::Flag(is_lower)),
+            (Symbol::IS_UPPER, AttrGetter::Flag(is_upper)),
+            (Symbol::IS_TITLE, AttrGetter::Flag(is_title)),
+            (Symbol::IS_SPACE, AttrGetter::Flag(is_space)),
+            (Symbol::IS_PUNCT, AttrGetter::Flag(is_punct)),
+            (Symbol::IS_BRACKET, AttrGetter::Flag(is_bracket)),
+            (Symbol::IS_QUOTE, AttrGetter::Flag(is_quote)),
+            (Symbol::IS_LEFT_PUNCT, AttrGetter::Flag(is_left_punct)),
+            (Symbol::IS_RIGHT_PUNCT, AttrGetter::Flag(is_right_punct)),
+            (Symbol::IS_CURRENCY, AttrGetter::Flag(is_currency)),
+            (Symbol::LIKE_URL, AttrGetter::Flag(like_url)),
+            (Symbol::LIKE_NUM, AttrGetter::Flag(like_num)),
+            (Symbol::LIKE_EMAIL, AttrGetter::Flag(like_email)),
+        ];
+        LexAttrs { getters: getters.into_iter().collect() }
+    }
+
+    /// The English getters (spaCy's `lang/en/lex_attrs.py`): the language-independent ones,
+    /// with `LIKE_NUM` also accepting number words and ordinals (see `english_like_num`).
+    pub fn english() -> Self {
+        LexAttrs::new().with_getter(Symbol::LIKE_NUM, AttrGetter::Flag(english_like_num))
+    }
+
+    /// The table `Token::get` uses: `english`, like the default tokenizer rules.
+    pub fn default_table() -> &'static LexAttrs {
+        &DEFAULT_TABLE
+    }
+
+    /// Sets (or replaces) the getter of the attribute `name`.
+    pub fn with_getter(mut self, name: Symbol, getter: AttrGetter) -> Self {
+        self.getters.insert(name, getter);
+        self
+    }
+
+    /// The value of the attribute `name` for `text`, or `None` if `name` is not a lexical
+    /// attribute of this table (e.g. `POS`, which needs a model).
+    pub fn get<'a>(&self, text: &'a str, name: Symbol) -> Option<AttrValue<'a>> {
+        self.getters.get(&name).map(|getter| getter.compute(text))
+    }
+
+    /// The attributes this table computes.
+    pub fn attrs(&self) -> impl Iterator<Item = Symbol> + '_ {
+        self.getters.keys().copied()
+    }
+}
+
+impl Default for LexAttrs {
+    fn default() -> Self {
+        LexAttrs::new()
+    }
+}
+
+// ----- Python string predicates -----
+
+fn py_is_alpha_char(c: char) -> bool {
+    use GeneralCategory::*;
+    matches!(get_general_category(c), UppercaseLetter | LowercaseLetter | TitlecaseLetter | ModifierLetter | OtherLetter)
+}
+
+/// Python's `str.isdigit` for one char: Numeric_Type Decimal (`Nd`) or Digit. The Digit chars
+/// (superscripts, circled digits and the like) are listed in full, from `DerivedNumericType.txt`.
+fn py_is_digit_char(c: char) -> bool {
+    get_general_category(c) == GeneralCategory::DecimalNumber
+        || matches!(c,
+            '\u{B2}' | '\u{B3}' | '\u{B9}' | '\u{1369}'..='\u{1371}' | '\u{19DA}' | '\u{2070}'
+            | '\u{2074}'..='\u{2079}' | '\u{2080}'..='\u{2089}' | '\u{2460}'..='\u{2468}'
+            | '\u{2474}'..='\u{247C}' | '\u{2488}'..='\u{2490}' | '\u{24EA}' | '\u{24F5}'..='\u{24FD}'
+            | '\u{24FF}' | '\u{2776}'..='\u{277E}' | '\u{2780}'..='\u{2788}' | '\u{278A}'..='\u{2792}'
+            | '\u{10A40}'..='\u{10A43}' | '\u{10E60}'..='\u{10E68}' | '\u{11052}'..='\u{1105A}'
+            | '\u{1F100}'..='\u{1F10A}')
+}
+
+fn py_is_title_char(c: char) -> bool {
+    get_general_category(c) == GeneralCategory::TitlecaseLetter
+}
+
+/// Python's `str.isspace` for one char: White_Space, plus the information separators.
+fn py_is_space_char(c: char) -> bool {
+    c.is_whitespace() || ('\u{1C}'..='\u{1F}').contains(&c)
+}
+
+fn py_is_digit(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(py_is_digit_char)
+}
+
+// ----- Getters -----
+
+pub fn orth(text: &str) -> Cow<'_, str> {
+    Cow::Borrowed(text)
+}
+
+pub fn lower(text: &str) -> Cow<'_, str> {
+    let lowered = text.to_lowercase();
+    if lowered == text { Cow::Borrowed(text) } else { Cow::Owned(lowered) }
+}
+
+/// The first char, as spaCy's `prefix`.
+pub fn prefix(text: &str) -> Cow<'_, str> {
+    Cow::Borrowed(text.char_indices().nth(1).map_or(text, |(i, _)| &text[..i]))
+}
+
+/// The last three chars, as spaCy's `suffix`.
+pub fn suffix(text: &str) -> Cow<'_, str> {
+    Cow::Borrowed(text.char_indices().rev().nth(2).map_or(text, |(i, _)| &text[i..]))
+}
+
+/// The length in chars (Python `len`).
+pub fn length(text: &str) -> usize {
+    text.chars().count()
+}
+
+/// spaCy's `word_shape`: letters (`str.isalpha`) become `X` if uppercase and `x` otherwise,
+/// digits (`str.isdigit`) `d`, other chars stay, and a run of the same shape char is cut after
+/// four. Texts of 100 chars or more are `LONG`. `tests/shape.rs` checks it against spaCy for
+/// every assigned char.
+pub fn word_shape(text: &str) -> Cow<'_, str> {
+    if text.chars().count() >= 100 {
+        return Cow::Borrowed("LONG");
+    }
+    let mut shape = String::with_capacity(text.len());
+    let mut last: Option<char> = None;
+    let mut seq = 0;
+    for c in text.chars() {
+        let shape_char = if py_is_alpha_char(c) {
+            if c.is_uppercase() { 'X' } else { 'x' }
+        } else if py_is_digit_char(c) {
+            'd'
+        } else {
+            c
+        };
+        if last == Some(shape_char) {
+            seq += 1;
+        } else {
+            seq = 0;
+            last = Some(shape_char);
+        }
+        if seq < 4 {
+            shape.push(shape_char);
+        }
+    }
+    Cow::Owned(shape)
+}
+
+pub fn is_alpha(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(py_is_alpha_char)
+}
+
+pub fn is_ascii(text: &str) -> bool {
+    text.is_ascii()
+}
+
+pub fn is_digit(text: &str) -> bool {
+    py_is_digit(text)
+}
+
+/// Python's `str.islower`: some cased char, and no uppercase or titlecase one.
+pub fn is_lower(text: &str) -> bool {
+    let mut cased = false;
+    for c in text.chars() {
+        if c.is_uppercase() || py_is_title_char(c) {
+            return false;
+        }
+        cased |= c.is_lowercase();
+    }
+    cased
+}
+
+/// Python's `str.isupper`: some cased char, and no lowercase or titlecase one.
+pub fn is_upper(text: &str) -> bool {
+    let mut cased = false;
+    for c in text.chars() {
+        if c.is_lowercase() || py_is_title_char(c) {
+            return false;
+        }
+        cased |= c.is_uppercase();
+    }
+    cased
+}
+
+/// Python's `str.istitle`: uppercase and titlecase chars only follow uncased ones, lowercase
+/// chars only cased ones, and there is at least one cased char.
+pub fn is_title(text: &str) -> bool {
+    let mut cased = false;
+    let mut previous_is_cased = false;
+    for c in text.chars() {
+        if c.is_uppercase() || py_is_title_char(c) {
+            if previous_is_cased {
+                return false;
+            }
+            previous_is_cased = true;
+            cased = true;
+        } else if c.is_lowercase() {
+            if !previous_is_cased {
+                return false;
+            }
+            previous_is_cased = true;
+            cased = true;
+        } else {
+            previous_is_cased = false;
+        }
+    }
+    cased
+}
+
+pub fn is_space(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(py_is_space_char)
+}
+
+/// Every char is punctuation (a `P*` general category).
+pub fn is_punct(text: &str) -> bool {
+    use GeneralCategory::*;
+    text.chars().all(|c| matches!(
+        get_general_category(c),
+        ConnectorPunctuation | DashPunctuation | OpenPunctuation | ClosePunctuation
+            | InitialPunctuation | FinalPunctuation | OtherPunctuation
+    ))
+}
+
+pub fn is_bracket(text: &str) -> bool {
+    matches!(text, "(" | ")" | "[" | "]" | "{" | "}" | "<" | ">")
+}
+
+pub fn is_quote(text: &str) -> bool {
+    matches!(
+        text,
+        "\"" | "'" | "`" | "«" | "»" | "‘" | "’" | "‚" | "‛" | "“" | "”" | "„" | "‟" | "‹" | "›" | "❮" | "❯" | "''" | "``"
+    )
+}
+
+pub fn is_left_punct(text: &str) -> bool {
+    matches!(
+        text,
+        "(" | "[" | "{" | "<" | "\"" | "'" | "«" | "‘" | "‚" | "‛" | "“" | "„" | "‟" | "‹" | "❮" | "``"
+    )
+}
+
+pub fn is_right_punct(text: &str) -> bool {
+    matches!(text, ")" | "]" | "}" | ">" | "\"" | "'" | "»" | "’" | "”" | "›" | "❯" | "''")
+}
+
+/// Every char is a currency symbol (`Sc`).
+pub fn is_currency(text: &str) -> bool {
+    text.chars().all(|c| get_general_category(c) == GeneralCategory::CurrencySymbol)
+}
+
+/// The number part of a `like_num` candidate: without a leading sign and without `,` and `.`.
+fn number_text(text: &str) -> String {
+    text.strip_prefix(['+', '-', '±', '~']).unwrap_or(text).replace([',', '.'], "")
+}
+
+/// Digits, or a `digits/digits` fraction.
+fn is_digits_or_fraction(text: &str) -> bool {
+    if py_is_digit(text) {
+        return true;
+    }
+    match text.split_once('/') {
+        Some((num, denom)) if !denom.contains('/') => py_is_digit(num) && py_is_digit(denom),
+        _ => false,
+    }
+}
+
+/// spaCy's language-independent `like_num`: an optional sign, then digits (`,` and `.` are
+/// ignored) or a `digits/digits` fraction.
+pub fn like_num(text: &str) -> bool {
+    is_digits_or_fraction(&number_text(text))
+}
+
+const ENGLISH_NUM_WORDS: &[&str] = &[
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven",
+    "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety", "hundred",
+    "thousand", "million", "billion", "trillion", "quadrillion", "gajillion", "bazillion",
+];
+
+const ENGLISH_ORDINAL_WORDS: &[&str] = &[
+    "first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth", "tenth",
+    "eleventh", "twelfth", "thirteenth", "fourteenth", "fifteenth", "sixteenth", "seventeenth",
+    "eighteenth", "nineteenth", "twentieth", "thirtieth", "fortieth", "fiftieth", "sixtieth",
+    "seventieth", "eightieth", "ninetieth", "hundredth", "thousandth", "millionth", "billionth",
+    "trillionth", "quadrillionth", "gajillionth", "bazillionth",
+];
+
+/// spaCy's English `like_num`: `like_num`, plus number words (`twenty`, `million`), ordinal
+/// words (`first`, `twentieth`) and digits with an ordinal suffix (`21st`, `1,000th`), in any case.
+pub fn english_like_num(text: &str) -> bool {
+    let text = number_text(text);
+    if is_digits_or_fraction(&text) {
+        return true;
+    }
+    let lower = text.to_lowercase();
+    if ENGLISH_NUM_WORDS.contains(&lower.as_str()) || ENGLISH_ORDINAL_WORDS.contains(&lower.as_str()) {
+        return true;
+    }
+    ["st", "nd", "rd", "th"].iter().any(|suffix| lower.strip_suffix(suffix).is_some_and(py_is_digit))
+}
+
+static LIKE_EMAIL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^([a-zA-Z0-9_.+-]+@[a-zA-Z0-9-]+\.[a-zA-Z0-9-.]+)").expect("valid e-mail pattern")
+});
+
+pub fn like_email(text: &str) -> bool {
+    LIKE_EMAIL.is_match(text)
+}
+
+/// Top-level domains `like_url` accepts after the last `.`, as spaCy's `_tlds`.
+const TLDS: &[&str] = &[
+    "com", "org", "edu", "gov", "net", "mil", "aero", "asia", "biz", "cat", "coop", "info", "int", "jobs",
+    "mobi", "museum", "name", "pro", "tel", "travel", "xxx", "ac", "ad", "ae", "af", "ag", "ai", "al", "am",
+    "an", "ao", "aq", "ar", "as", "at", "au", "aw", "ax", "az", "ba", "bb", "bd", "be", "bf", "bg", "bh", "bi",
+    "bj", "bm", "bn", "bo", "br", "bs", "bt", "bv", "bw", "by", "bz", "ca", "cc", "cd", "cf", "cg", "ch", "ci",
+    "ck", "cl", "cm", "cn", "co", "cr", "cs", "cu", "cv", "cx", "cy", "cz", "dd", "de", "dj", "dk", "dm", "do",
+    "dz", "ec", "ee", "eg", "eh", "er", "es", "et", "eu", "fi", "fj", "fk", "fm", "fo", "fr", "ga", "gb", "gd",
+    "ge", "gf", "gg", "gh", "gi", "gl", "gm", "gn", "gp", "gq", "gr", "gs", "gt", "gu", "gw", "gy", "hk", "hm",
+    "hn", "hr", "ht", "hu", "id", "ie", "il", "im", "in", "io", "iq", "ir", "is", "it", "je", "jm", "jo", "jp",
+    "ke", "kg", "kh", "ki", "km", "kn", "kp", "kr", "kw", "ky", "kz", "la", "lb", "lc", "li", "lk", "lr", "ls",
+    "lt", "lu", "lv", "ly", "ma", "mc", "md", "me", "mg", "mh", "mk", "ml", "mm", "mn", "mo", "mp", "mq", "mr",
+    "ms", "mt", "mu", "mv", "mw", "mx", "my", "mz", "na", "nc", "ne", "nf", "ng", "ni", "nl", "no", "np", "nr",
+    "nu", "nz", "om", "pa", "pe", "pf", "pg", "ph", "pk", "pl", "pm", "pn", "pr", "ps", "pt", "pw", "py", "qa",
+    "re", "ro", "rs", "ru", "rw", "sa", "sb", "sc", "sd", "se", "sg", "sh", "si", "sj", "sk", "sl", "sm", "sn",
+    "so", "sr", "ss", "st", "su", "sv", "sy", "sz", "tc", "td", "tf", "tg", "th", "tj", "tk", "tl", "tm", "tn",
+    "to", "tp", "tr", "tt", "tv", "tw", "tz", "ua", "ug", "uk", "us", "uy", "uz", "va", "vc", "ve", "vg", "vi",
+    "vn", "vu", "wf", "ws", "ye", "yt", "za", "zm", "zw",
+];
+
+static URL_MATCH: Lazy<Option<FancyRegex>> = Lazy::new(|| FancyRegex::new(&pattern::get_english_url_match_pattern_str()).ok());
+
+/// spaCy's `like_url`: anything starting with a scheme or `www.`, a dotted name ending in a known
+/// top-level domain or a `/`, or a match of the tokenizer's URL pattern. E-mail addresses are not URLs.
+pub fn like_url(text: &str) -> bool {
+    if text.starts_with("http://") || text.starts_with("https://") {
+        return true;
+    }
+    if text.starts_with("www.") && text.chars().count() >= 5 {
+        return true;
+    }
+    if text.is_empty() || text.starts_with('.') || text.ends_with('.') || text.contains('@') {
+        return false;
+    }
+    let Some((_, tld)) = text.rsplit_once('.') else { return false };
+    let tld = tld.split(':').next().unwrap_or(tld);
+    if tld.ends_with('/') {
+        return true;
+    }
+    if is_alpha(tld) && TLDS.contains(&tld) {
+        return true;
+    }
+    URL_MATCH.as_ref().is_some_and(|re| re.is_match(text).unwrap_or(false))
+}
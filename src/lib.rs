@@ -0,0 +1,56 @@
+// src/lib.rs
+//! spaCy-like English tokenizer.
+//!
+//! The rule data lives in `pattern`, and the compiled rules and the tokenization pipeline
+//! live in `tokenizer`. Its output is the `Token` type (and its borrowing counterpart
+//! `TokenRef`) in `token`, or the lossless `Doc` container in `doc`.
+//!
+//! Rule sets can be loaded from JSON/TOML files via `config` or imported from a saved spaCy
+//! tokenizer via `spacy_import`; rule loading errors are `TokenizerError`s. `classify` routes
+//! each pattern to Aho-Corasick, `regex` or `fancy_regex`.
+//!
+//! `Tokenizer::explain` reports the rule behind each token (see `explain`), and `trace`
+//! streams structured debug events to a pluggable sink. `stream` tokenizes a `BufRead` in
+//! bounded memory, and `cache` memoizes the split of repeated chunks.
+//!
+//! Token offsets come in every unit of `offsets::OffsetUnit` (bytes, chars, UTF-16 code
+//! units, grapheme clusters). `lex_attrs` computes spaCy's lexical attributes, keyed by the
+//! `symbols::Symbol` table, and `strings` gives strings spaCy's 64-bit ids, for
+//! `Doc::to_array`.
+//!
+//! The `main` binary is a thin wrapper over `Tokenizer`.
+
+mod affix;
+pub mod cache;
+pub mod classify;
+pub mod config;
+pub mod doc;
+pub mod error;
+pub mod explain;
+mod fastpath;
+pub mod lex_attrs;
+pub mod offsets;
+pub mod pattern;
+pub mod spacy_import;
+pub mod stream;
+pub mod strings;
+pub mod symbols;
+pub mod token;
+pub mod tokenizer;
+pub mod trace;
+
+pub use cache::CacheStats;
+pub use classify::{ClassificationReport, PatternClass, PatternClassification};
+pub use config::{ConfigFormat, RulesConfig};
+pub use doc::Doc;
+pub use error::{RegexErrorPolicy, RuleError, RuleKind, TokenizerError};
+pub use explain::{Explanation, TokenRule};
+pub use lex_attrs::{AttrValue, LexAttrs};
+pub use offsets::OffsetUnit;
+pub use spacy_import::ImportReport;
+pub use stream::TokenStream;
+pub use strings::StringStore;
+pub use symbols::Symbol;
+pub use token::{Token, TokenRef};
+pub use tokenizer::{Tokenizer, TokenizerRules};
+pub use trace::{CollectTraceSink, StderrTraceSink, TraceEvent, TraceSink, TraceStage};
@@ -0,0 +1,135 @@
+// src/offsets.rs
+//! Positions in the input, tracked in every offset unit at once.
+//!
+//! The text is walked once (see `Tokenizer::tokenize`): each chunk's position is taken from
+//! the walk, and token positions advance from their chunk's, so no offset is ever recounted.
+//! The walk goes by extended grapheme clusters (`unicode-segmentation`), and token boundaries
+//! never fall inside one, so grapheme offsets are exact too.
+
+use std::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The unit token offsets are counted in, for `Token::span`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum OffsetUnit {
+    /// UTF-8 bytes, for slicing the Rust string.
+    Byte,
+    /// Unicode scalar values (Rust `char`s, Python `str` indices).
+    Char,
+    /// UTF-16 code units (JavaScript and Java string indices).
+    Utf16,
+    /// Extended grapheme clusters (user-perceived characters).
+    Grapheme,
+}
+
+/// A position in the input in every `OffsetUnit`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub(crate) struct TextPos {
+    pub(crate) byte: usize,
+    pub(crate) char: usize,
+    pub(crate) utf16: usize,
+    pub(crate) grapheme: usize,
+}
+
+impl TextPos {
+    pub(crate) fn get(&self, unit: OffsetUnit) -> usize {
+        match unit {
+            OffsetUnit::Byte => self.byte,
+            OffsetUnit::Char => self.char,
+            OffsetUnit::Utf16 => self.utf16,
+            OffsetUnit::Grapheme => self.grapheme,
+        }
+    }
+
+    /// Moves past the grapheme cluster `cluster`.
+    pub(crate) fn advance_cluster(&mut self, cluster: &str) {
+        self.byte += cluster.len();
+        self.grapheme += 1;
+        if cluster.len() == 1 {
+            self.char += 1;
+            self.utf16 += 1;
+        } else {
+            for c in cluster.chars() {
+                self.char += 1;
+                self.utf16 += c.len_utf16();
+            }
+        }
+    }
+
+    /// Moves past `s`, which is ASCII without `"\r\n"` (one byte, char, code unit and
+    /// grapheme cluster per char).
+    pub(crate) fn advance_ascii(&mut self, s: &str) {
+        debug_assert!(s.is_ascii() && !s.contains("\r\n"));
+        self.byte += s.len();
+        self.char += s.len();
+        self.utf16 += s.len();
+        self.grapheme += s.len();
+    }
+
+    /// Moves past `s`, which starts and ends on grapheme cluster boundaries.
+    pub(crate) fn advance_str(&mut self, s: &str) {
+        for cluster in clusters(s) {
+            self.advance_cluster(cluster);
+        }
+    }
+}
+
+/// The range between two positions in `unit`.
+pub(crate) fn span(start: TextPos, end: TextPos, unit: OffsetUnit) -> Range<usize> {
+    start.get(unit)..end.get(unit)
+}
+
+/// The extended grapheme clusters of `text`, in order.
+pub(crate) fn clusters(text: &str) -> Clusters<'_> {
+    Clusters { rest: text }
+}
+
+/// Iterator of `clusters`. Between two ASCII chars there is always a cluster boundary (except
+/// inside `"\r\n"`), so the segmentation rules only run where a non-ASCII char is involved,
+/// starting afresh at the last boundary.
+pub(crate) struct Clusters<'t> {
+    rest: &'t str,
+}
+
+impl<'t> Iterator for Clusters<'t> {
+    type Item = &'t str;
+
+    fn next(&mut self) -> Option<&'t str> {
+        let len = match self.rest.as_bytes() {
+            [] => return None,
+            [b'\r', b'\n', ..] => 2,
+            [first, after @ ..] if first.is_ascii() && after.first().is_none_or(u8::is_ascii) => 1,
+            _ => self.rest.graphemes(true).next().map_or(self.rest.len(), str::len),
+        };
+        let (cluster, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        Some(cluster)
+    }
+}
+
+/// Merges adjacent byte `spans` of `chunk` (covering it in order) whose shared boundary falls
+/// inside a grapheme cluster, such as a letter and its combining marks or an emoji ZWJ sequence.
+/// Returns, for each original span, whether it starts a merged span; `None` if nothing was merged.
+pub(crate) fn merge_split_clusters(chunk: &str, spans: &mut Vec<(usize, usize)>) -> Option<Vec<bool>> {
+    // ASCII chunks hold no whitespace, so no `"\r\n"` either: every boundary is a cluster boundary.
+    if spans.len() < 2 || chunk.is_ascii() {
+        return None;
+    }
+    let mut boundaries = chunk.grapheme_indices(true).map(|(i, _)| i).peekable();
+    let mut starts_span = Vec::with_capacity(spans.len());
+    let mut merged: Vec<(usize, usize)> = Vec::with_capacity(spans.len());
+    for &(start, end) in spans.iter() {
+        while boundaries.next_if(|&b| b < start).is_some() {}
+        match merged.last_mut() {
+            Some(last) if boundaries.peek() != Some(&start) => last.1 = end,
+            _ => merged.push((start, end)),
+        }
+        starts_span.push(merged.last().is_some_and(|last| last.0 == start));
+    }
+    if merged.len() == spans.len() {
+        return None;
+    }
+    *spans = merged;
+    Some(starts_span)
+}
@@ -13,6 +13,9 @@ pub type ExceptionAttributeMap = HashMap<String, String>;
 pub type ExceptionTokenList = Vec<ExceptionAttributeMap>;
 pub type ExceptionMap = HashMap<String, ExceptionTokenList>;
 
+// (orth, optional norm) pairs describing how a special-case key is split.
+type OrthNormParts<'a> = Vec<(&'a str, Option<&'a str>)>;
+
 pub static EMOTICONS: &[&str] = &[
     ":)", ":-)", ":))", ":-))", ":)))", ":-)))", "(:", "(-:", "=)", "(=", ":]", ":-]", "[:", "[-:", "[=", "=]",
     ":o)", "(o:", ":}", ":-}", "8)", "8-)", "(-8", ";)", ";-)", "(;", "(-;", ":(", ":-(", ":((", ":-((", ":(((", ":-(((",
@@ -274,7 +277,7 @@ fn get_english_tokenizer_exceptions_inner() -> ExceptionMap {
         }
     }
 
-    let other_exc_map_data: Vec<(&str, Vec<(&str, Option<&str>)>)> = vec![
+    let other_exc_map_data: Vec<(&str, OrthNormParts)> = vec![
         ("y'all", vec![("y'", Some("you")), ("all", None)]),
         ("yall", vec![("y", Some("you")), ("all", None)]),
         ("how'd'y", vec![("how", None), ("'d", None), ("'y", Some("you"))]),
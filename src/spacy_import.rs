@@ -0,0 +1,337 @@
+// src/spacy_import.rs
+//! Import of spaCy's serialized tokenizer (`nlp.tokenizer.to_disk()` / `to_bytes()`).
+//!
+//! The artifact is a msgpack map (or the same map as JSON) with the keys `prefix_search`,
+//! `suffix_search`, `infix_finditer`, `token_match`, `url_match` and `exceptions`. The regex
+//! values are Python `re` patterns, so they are translated to `fancy_regex` syntax first.
+//! Whatever still fails to compile is reported in an `ImportReport` instead of aborting.
+
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::classify;
+use crate::config::{RulesConfig, SpecialCaseToken};
+use crate::error::{RuleKind, TokenizerError};
+use crate::pattern::{ExceptionAttributeMap, NORM, ORTH};
+use crate::symbols::Symbol;
+use crate::tokenizer::validate_special_case;
+
+/// A pattern (or special-case attribute) from the spaCy artifact that could not be imported.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UntranslatedPattern {
+    pub kind: Option<RuleKind>,
+    pub pattern: String,
+    pub reason: String,
+}
+
+/// What happened while importing a spaCy tokenizer.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Patterns that were rewritten from Python `re` syntax, as (original, translated).
+    pub translated: Vec<(String, String)>,
+    /// Combined patterns that did not compile as a whole and were split into their alternatives.
+    pub split_patterns: Vec<RuleKind>,
+    pub untranslated: Vec<UntranslatedPattern>,
+}
+
+impl ImportReport {
+    pub fn is_lossless(&self) -> bool {
+        self.untranslated.is_empty()
+    }
+}
+
+/// Reads a spaCy tokenizer artifact from disk and converts it into a `RulesConfig`.
+///
+/// `path` may be the `tokenizer` file itself, a `tokenizer.json` file, or a pipeline directory
+/// containing a `tokenizer` file. Files ending in `.json` are parsed as JSON, anything else as msgpack.
+pub fn import_spacy_tokenizer<P: AsRef<Path>>(path: P) -> Result<(RulesConfig, ImportReport), TokenizerError> {
+    let mut path = path.as_ref().to_path_buf();
+    if path.is_dir() {
+        path.push("tokenizer");
+    }
+    let bytes = fs::read(&path)?;
+    let is_json = path.extension().and_then(|e| e.to_str()).is_some_and(|e| e.eq_ignore_ascii_case("json"));
+    if is_json {
+        let value: Value = serde_json::from_slice(&bytes)
+            .map_err(|e| TokenizerError::InvalidConfig(format!("invalid spaCy tokenizer JSON: {}", e)))?;
+        import_spacy_tokenizer_value(&value)
+    } else {
+        import_spacy_tokenizer_msgpack(&bytes)
+    }
+}
+
+/// Converts the msgpack bytes of `nlp.tokenizer.to_bytes()` into a `RulesConfig`.
+pub fn import_spacy_tokenizer_msgpack(bytes: &[u8]) -> Result<(RulesConfig, ImportReport), TokenizerError> {
+    let value = rmpv::decode::read_value(&mut &bytes[..])
+        .map_err(|e| TokenizerError::InvalidConfig(format!("invalid spaCy tokenizer msgpack: {}", e)))?;
+    import_spacy_tokenizer_value(&msgpack_to_json(value))
+}
+
+fn import_spacy_tokenizer_value(value: &Value) -> Result<(RulesConfig, ImportReport), TokenizerError> {
+    let map = value
+        .as_object()
+        .ok_or_else(|| TokenizerError::InvalidConfig("spaCy tokenizer data is not a map".to_string()))?;
+    let mut report = ImportReport::default();
+    let mut config = RulesConfig::default();
+
+    let pattern_of = |key: &str| map.get(key).and_then(Value::as_str).filter(|s| !s.is_empty());
+
+    if let Some(p) = pattern_of("prefix_search") {
+        config.prefixes = import_pattern_list(RuleKind::Prefix, p, &mut report);
+    }
+    if let Some(p) = pattern_of("suffix_search") {
+        config.suffixes = import_pattern_list(RuleKind::Suffix, p, &mut report);
+    }
+    if let Some(p) = pattern_of("infix_finditer") {
+        config.infixes = import_pattern_list(RuleKind::Infix, p, &mut report);
+    }
+    if let Some(p) = pattern_of("token_match") {
+        config.token_match = import_single_pattern(RuleKind::TokenMatch, p, &mut report);
+    }
+    if let Some(p) = pattern_of("url_match") {
+        config.url_match = import_single_pattern(RuleKind::UrlMatch, p, &mut report);
+    }
+
+    if let Some(exceptions) = map.get("exceptions").and_then(Value::as_object) {
+        for (key, tokens) in exceptions {
+            let Some(tokens) = tokens.as_array() else { continue };
+            let mut special_case: Vec<SpecialCaseToken> = Vec::with_capacity(tokens.len());
+            for token in tokens.iter().filter_map(Value::as_object) {
+                let mut attrs = SpecialCaseToken::new();
+                for (attr, attr_value) in token {
+                    let attr_name = match special_case_attr(attr) {
+                        Some(Symbol::ORTH) => ORTH,
+                        Some(Symbol::NORM) => NORM,
+                        _ => {
+                            report.untranslated.push(UntranslatedPattern {
+                                kind: None,
+                                pattern: key.clone(),
+                                reason: format!("unsupported special-case attribute '{}'", attr),
+                            });
+                            continue;
+                        }
+                    };
+                    if let Some(s) = attr_value.as_str() {
+                        attrs.insert(attr_name.to_string(), s.to_string());
+                    }
+                }
+                special_case.push(attrs);
+            }
+            let as_exception: Vec<ExceptionAttributeMap> =
+                special_case.iter().map(|attrs| attrs.iter().map(|(k, v)| (k.clone(), v.clone())).collect()).collect();
+            if let Err(e) = validate_special_case(key, &as_exception) {
+                report.untranslated.push(UntranslatedPattern { kind: None, pattern: key.clone(), reason: e.to_string() });
+                continue;
+            }
+            config.special_cases.insert(key.clone(), special_case);
+        }
+    }
+
+    Ok((config, report))
+}
+
+/// Imports a combined spaCy pattern (`a|b|c`) as a rule list.
+///
+/// The pattern is kept whole when it compiles, which preserves spaCy's leftmost-first
+/// semantics exactly. Otherwise it is split into its top-level alternatives and only the
+/// alternatives that fail are dropped and reported.
+fn import_pattern_list(kind: RuleKind, pattern: &str, report: &mut ImportReport) -> Vec<String> {
+    let translated = translate_python_regex(pattern);
+    if classify::fancy_regex(&translated).is_ok() {
+        if translated != pattern {
+            report.translated.push((pattern.to_string(), translated.clone()));
+        }
+        return vec![translated];
+    }
+
+    report.split_patterns.push(kind);
+    split_top_level_alternatives(pattern)
+        .into_iter()
+        .filter_map(|alternative| import_single_pattern(kind, alternative, report))
+        .collect()
+}
+
+fn import_single_pattern(kind: RuleKind, pattern: &str, report: &mut ImportReport) -> Option<String> {
+    let translated = translate_python_regex(pattern);
+    match classify::fancy_regex(&translated) {
+        Ok(_) => {
+            if translated != pattern {
+                report.translated.push((pattern.to_string(), translated.clone()));
+            }
+            Some(translated)
+        }
+        Err(e) => {
+            report.untranslated.push(UntranslatedPattern {
+                kind: Some(kind),
+                pattern: pattern.to_string(),
+                reason: e.to_string(),
+            });
+            None
+        }
+    }
+}
+
+/// The attribute of a serialized special-case key: a symbol id in spaCy's `to_bytes()`
+/// output (`"65"` for `ORTH`), a symbol name in JSON artifacts.
+fn special_case_attr(key: &str) -> Option<Symbol> {
+    key.parse().ok().and_then(Symbol::from_id).or_else(|| Symbol::from_str(key))
+}
+
+/// Rewrites the Python `re` constructs that `fancy_regex` spells differently or rejects:
+///
+/// * `\Z` becomes `\z`, and `(?#...)` comments and the redundant `(?u)` flag are dropped;
+/// * `{` and `}` that do not form a quantifier are escaped, and `{,n}`/`{,}` become `{0,n}`/`{0,}`;
+/// * inside character classes, `[` and the set operators `&&`, `--`, `~~` are escaped.
+pub fn translate_python_regex(pattern: &str) -> String {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut out = String::with_capacity(pattern.len());
+    let mut in_class = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\\' => {
+                match chars.get(i + 1) {
+                    Some('Z') => out.push_str(r"\z"),
+                    Some(&next) => {
+                        out.push('\\');
+                        out.push(next);
+                    }
+                    None => out.push('\\'),
+                }
+                i += 2;
+                continue;
+            }
+            '[' if in_class => out.push_str(r"\["),
+            '[' => {
+                in_class = true;
+                out.push('[');
+                // A leading `^` and a leading `]` are part of the class syntax, not its end.
+                if chars.get(i + 1) == Some(&'^') {
+                    out.push('^');
+                    i += 1;
+                }
+                if chars.get(i + 1) == Some(&']') {
+                    out.push_str(r"\]");
+                    i += 1;
+                }
+            }
+            ']' if in_class => {
+                in_class = false;
+                out.push(']');
+            }
+            '&' | '-' | '~' if in_class && chars.get(i + 1) == Some(&c) => {
+                out.push(c);
+                out.push('\\');
+                out.push(c);
+                i += 2;
+                continue;
+            }
+            '(' if !in_class && chars[i..].starts_with(&['(', '?', '#']) => {
+                while i < chars.len() && chars[i] != ')' {
+                    i += 1;
+                }
+            }
+            '(' if !in_class && chars[i..].starts_with(&['(', '?', 'u', ')']) => {
+                i += 3;
+            }
+            '{' if !in_class => match quantifier_len(&chars[i..]) {
+                Some((len, true)) => {
+                    // Python's `{,n}` means `{0,n}`, and `{,}` means `{0,}`.
+                    out.push_str("{0");
+                    out.extend(&chars[i + 1..i + len]);
+                    i += len;
+                    continue;
+                }
+                Some((len, false)) => {
+                    out.extend(&chars[i..i + len]);
+                    i += len;
+                    continue;
+                }
+                None => out.push_str(r"\{"),
+            },
+            '}' if !in_class => out.push_str(r"\}"),
+            _ => out.push(c),
+        }
+        i += 1;
+    }
+    out
+}
+
+/// If `chars` starts with a quantifier (`{n}`, `{n,}`, `{n,m}`, `{,m}` or `{,}`), returns its length
+/// and whether the lower bound is missing.
+fn quantifier_len(chars: &[char]) -> Option<(usize, bool)> {
+    let close = chars.iter().position(|&c| c == '}')?;
+    let body: String = chars[1..close].iter().collect();
+    let (lower, upper) = match body.split_once(',') {
+        Some((lower, upper)) => (lower, Some(upper)),
+        None => (body.as_str(), None),
+    };
+    let is_num = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    let valid = match upper {
+        None => is_num(lower),
+        Some(upper) => (is_num(lower) || lower.is_empty()) && (upper.is_empty() || is_num(upper)),
+    };
+    valid.then_some((close + 1, lower.is_empty()))
+}
+
+/// Splits a pattern on `|` at nesting depth zero, outside character classes and escapes.
+fn split_top_level_alternatives(pattern: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut in_class = false;
+    let mut escaped = false;
+    let mut start = 0;
+    for (i, c) in pattern.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '[' if !in_class => in_class = true,
+            ']' if in_class => in_class = false,
+            '(' if !in_class => depth += 1,
+            ')' if !in_class => depth = depth.saturating_sub(1),
+            '|' if !in_class && depth == 0 => {
+                parts.push(&pattern[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&pattern[start..]);
+    parts
+}
+
+/// Converts a decoded msgpack value into JSON, turning integer map keys (spaCy symbol IDs) into strings.
+fn msgpack_to_json(value: rmpv::Value) -> Value {
+    match value {
+        rmpv::Value::Nil => Value::Null,
+        rmpv::Value::Boolean(b) => Value::Bool(b),
+        rmpv::Value::Integer(n) => n.as_i64().map(Value::from).or_else(|| n.as_u64().map(Value::from)).unwrap_or(Value::Null),
+        rmpv::Value::F32(f) => Value::from(f as f64),
+        rmpv::Value::F64(f) => Value::from(f),
+        rmpv::Value::String(s) => s.into_str().map(Value::String).unwrap_or(Value::Null),
+        rmpv::Value::Array(items) => Value::Array(items.into_iter().map(msgpack_to_json).collect()),
+        rmpv::Value::Map(entries) => {
+            let object: serde_json::Map<String, Value> = entries
+                .into_iter()
+                .filter_map(|(k, v)| {
+                    let key = match k {
+                        rmpv::Value::String(s) => s.into_str()?,
+                        rmpv::Value::Integer(n) => n.to_string(),
+                        _ => return None,
+                    };
+                    Some((key, msgpack_to_json(v)))
+                })
+                .collect();
+            Value::Object(object)
+        }
+        // Binary blobs (e.g. the serialized vocab) and extension types carry no tokenizer rules.
+        rmpv::Value::Binary(_) | rmpv::Value::Ext(..) => Value::Null,
+    }
+}
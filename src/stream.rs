@@ -0,0 +1,127 @@
+// src/stream.rs
+//! Streaming tokenization of a `BufRead`, for inputs too large to hold in memory.
+//!
+//! `TokenStream` reads whole lines until a batch reaches its byte budget, tokenizes the
+//! batch (lines in parallel, see `Tokenizer::tokenize`) and yields its tokens in order
+//! before reading the next one, so memory stays proportional to the batch size.
+//! The tokens and their offsets are the same as `Tokenizer::tokenize` on the whole input.
+
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+use crate::error::{RuleError, TokenizerError};
+use crate::offsets::TextPos;
+use crate::token::Token;
+use crate::tokenizer::Tokenizer;
+
+/// Default byte budget of a batch.
+pub const DEFAULT_BATCH_BYTES: usize = 1 << 20;
+
+/// An iterator over the tokens of a `BufRead`, from `Tokenizer::tokenize_reader`.
+///
+/// Offsets are relative to the start of the stream. Reading stops at the first error
+/// (I/O, invalid UTF-8, or a rule failure under `RegexErrorPolicy::Fail`), which is yielded once.
+pub struct TokenStream<R> {
+    tokenizer: Tokenizer,
+    reader: R,
+    batch_bytes: usize,
+    /// Text of the next batch; starts with the whitespace held back from the previous one.
+    buffer: String,
+    /// Position of `buffer` within the stream.
+    buffer_pos: TextPos,
+    pending: VecDeque<Token>,
+    rule_errors: Vec<RuleError>,
+    eof: bool,
+    failed: bool,
+}
+
+impl<R: BufRead> TokenStream<R> {
+    pub(crate) fn new(tokenizer: Tokenizer, reader: R) -> Self {
+        TokenStream {
+            tokenizer,
+            reader,
+            batch_bytes: DEFAULT_BATCH_BYTES,
+            buffer: String::new(),
+            buffer_pos: TextPos::default(),
+            pending: VecDeque::new(),
+            rule_errors: Vec::new(),
+            eof: false,
+            failed: false,
+        }
+    }
+
+    /// Sets the byte budget of a batch (default `DEFAULT_BATCH_BYTES`). Batches always end on a
+    /// line boundary, so a batch holds at least one whole line whatever the budget.
+    pub fn with_batch_bytes(mut self, batch_bytes: usize) -> Self {
+        self.batch_bytes = batch_bytes.max(1);
+        self
+    }
+
+    /// Rules that failed at match time and were skipped under the tokenizer's `RegexErrorPolicy`,
+    /// for the batches read so far.
+    pub fn rule_errors(&self) -> &[RuleError] {
+        &self.rule_errors
+    }
+
+    /// Reads and tokenizes the next batch into `pending`. Returns `false` at the end of the input.
+    fn fill_batch(&mut self) -> Result<bool, TokenizerError> {
+        let carried = self.buffer.len();
+        while !self.eof && self.buffer.len() - carried < self.batch_bytes {
+            if self.reader.read_line(&mut self.buffer)? == 0 {
+                self.eof = true;
+            }
+        }
+        if self.buffer.is_empty() {
+            return Ok(false);
+        }
+
+        let trace_sink = self.tokenizer.trace_sink();
+        let (tokens, rule_errors) = self.tokenizer.tokenize_lines(&self.buffer, self.buffer_pos, trace_sink, true)?;
+        self.rule_errors.extend(rule_errors);
+
+        // A whitespace run is a single token, so trailing whitespace may continue in the next
+        // batch: hold it back and tokenize it again with what follows.
+        let held = match tokens.last() {
+            Some(last) if !self.eof && last.text.chars().all(char::is_whitespace) => Some(last.start_pos()),
+            _ => None,
+        };
+        let emitted = tokens.len() - usize::from(held.is_some());
+        self.pending.extend(tokens[..emitted].iter().map(|t| t.to_token()));
+
+        // The tokens cover the whole buffer, so the next batch starts where the last one ends.
+        match held {
+            Some(start) => {
+                self.buffer.drain(..start.byte - self.buffer_pos.byte);
+                self.buffer_pos = start;
+            }
+            None => {
+                if let Some(last) = tokens.last() {
+                    self.buffer_pos = last.end_pos();
+                }
+                self.buffer.clear();
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl<R: BufRead> Iterator for TokenStream<R> {
+    type Item = Result<Token, TokenizerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            if self.failed {
+                return None;
+            }
+            match self.fill_batch() {
+                Ok(true) => {}
+                Ok(false) => return None,
+                Err(e) => {
+                    self.failed = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        self.pending.pop_front().map(Ok)
+    }
+}
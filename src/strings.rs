@@ -0,0 +1,108 @@
+// src/strings.rs
+//! String ids compatible with spaCy's `StringStore`.
+//!
+//! spaCy refers to every string (ORTH, LOWER, NORM, TAG values...) by a 64-bit id: the
+//! symbol's value for the names in `symbols.rs`, 0 for the empty string, and otherwise the
+//! MurmurHash64A of the UTF-8 bytes with seed 1. `StringStore` computes the same ids and
+//! interns the strings so ids can be turned back into text, as in `Doc::to_array`.
+
+use std::collections::HashMap;
+
+use crate::symbols::Symbol;
+
+/// MurmurHash64A (Austin Appleby), reading blocks little-endian as spaCy's `murmurhash` does
+/// on the usual platforms.
+pub fn murmurhash64a(key: &[u8], seed: u64) -> u64 {
+    const M: u64 = 0xc6a4_a793_5bd1_e995;
+    const R: u32 = 47;
+
+    let mut h = seed ^ (key.len() as u64).wrapping_mul(M);
+    let mut blocks = key.chunks_exact(8);
+    for block in &mut blocks {
+        let mut k = u64::from_le_bytes(block.try_into().expect("8-byte block"));
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h ^= k;
+        h = h.wrapping_mul(M);
+    }
+    let tail = blocks.remainder();
+    if !tail.is_empty() {
+        for (i, &byte) in tail.iter().enumerate() {
+            h ^= u64::from(byte) << (8 * i);
+        }
+        h = h.wrapping_mul(M);
+    }
+    h ^= h >> R;
+    h = h.wrapping_mul(M);
+    h ^= h >> R;
+    h
+}
+
+/// spaCy's `hash_string`: the MurmurHash64A of the UTF-8 bytes with seed 1.
+pub fn hash_string(s: &str) -> u64 {
+    murmurhash64a(s.as_bytes(), 1)
+}
+
+/// The id spaCy gives `s`: 0 for `""`, the symbol value for a symbol name, the hash otherwise.
+pub fn string_id(s: &str) -> u64 {
+    if s.is_empty() {
+        0
+    } else if let Some(symbol) = Symbol::from_str(s) {
+        symbol.id()
+    } else {
+        hash_string(s)
+    }
+}
+
+/// Interned strings by id, as spaCy's `StringStore`. Symbol names and `""` are never stored:
+/// their ids resolve without it.
+#[derive(Clone, Debug, Default)]
+pub struct StringStore {
+    strings: HashMap<u64, Box<str>>,
+}
+
+impl StringStore {
+    pub fn new() -> Self {
+        StringStore::default()
+    }
+
+    /// Interns `s` and returns its id (see `string_id`).
+    pub fn add(&mut self, s: &str) -> u64 {
+        let id = string_id(s);
+        if id != 0 && Symbol::from_str(s).is_none() {
+            self.strings.entry(id).or_insert_with(|| s.into());
+        }
+        id
+    }
+
+    /// The string with id `id`: a symbol name, `""` for 0, or an interned string.
+    pub fn get(&self, id: u64) -> Option<&str> {
+        if id == 0 {
+            return Some("");
+        }
+        if let Some(s) = self.strings.get(&id) {
+            return Some(s);
+        }
+        Symbol::from_id(id).map(|symbol| symbol.as_str())
+    }
+
+    /// Whether `s` has an id that `get` resolves: a symbol name, `""` or an interned string.
+    pub fn contains(&self, s: &str) -> bool {
+        self.get(string_id(s)) == Some(s)
+    }
+
+    /// Number of interned strings (symbol names are not counted).
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// The interned strings with their ids, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (u64, &str)> + '_ {
+        self.strings.iter().map(|(&id, s)| (id, &**s))
+    }
+}
@@ -1,915 +1,635 @@
 //! Canonical Symbol Table for NLP Attributes (spaCy-style)
 //!
-//! Enum + string keys + mappings
+//! `Symbol` mirrors spaCy's `symbols.pxd`: each name has the same numeric id as in spaCy, so
+//! ids can be exchanged with spaCy (see `strings`). Everything (the enum, its discriminants,
+//! `as_str`, `from_str`, `iter` and `all_symbol_names`) is generated by `symbols!` from the
+//! one table below; add new names at the end, with the id spaCy gives them.
 
-// String constants (export these for dict keys etc.)
-pub const NIL: &str = "NIL";
-pub const IS_ALPHA: &str = "IS_ALPHA";
-pub const IS_ASCII: &str = "IS_ASCII";
-pub const IS_DIGIT: &str = "IS_DIGIT";
-pub const IS_LOWER: &str = "IS_LOWER";
-pub const IS_PUNCT: &str = "IS_PUNCT";
-pub const IS_SPACE: &str = "IS_SPACE";
-pub const IS_TITLE: &str = "IS_TITLE";
-pub const IS_UPPER: &str = "IS_UPPER";
-pub const LIKE_URL: &str = "LIKE_URL";
-pub const LIKE_NUM: &str = "LIKE_NUM";
-pub const LIKE_EMAIL: &str = "LIKE_EMAIL";
-pub const IS_STOP: &str = "IS_STOP";
-pub const IS_OOV_DEPRECATED: &str = "IS_OOV_DEPRECATED";
-pub const IS_BRACKET: &str = "IS_BRACKET";
-pub const IS_QUOTE: &str = "IS_QUOTE";
-pub const IS_LEFT_PUNCT: &str = "IS_LEFT_PUNCT";
-pub const IS_RIGHT_PUNCT: &str = "IS_RIGHT_PUNCT";
-pub const IS_CURRENCY: &str = "IS_CURRENCY";
-
-pub const ID: &str = "ID";
-pub const ORTH: &str = "ORTH";
-pub const LOWER: &str = "LOWER";
-pub const NORM: &str = "NORM";
-pub const SHAPE: &str = "SHAPE";
-pub const PREFIX: &str = "PREFIX";
-pub const SUFFIX: &str = "SUFFIX";
-pub const LENGTH: &str = "LENGTH";
-pub const CLUSTER: &str = "CLUSTER";
-pub const LEMMA: &str = "LEMMA";
-pub const POS: &str = "POS";
-pub const TAG: &str = "TAG";
-pub const DEP: &str = "DEP";
-pub const ENT_IOB: &str = "ENT_IOB";
-pub const ENT_TYPE: &str = "ENT_TYPE";
-pub const HEAD: &str = "HEAD";
-pub const SENT_START: &str = "SENT_START";
-pub const SPACY: &str = "SPACY";
-pub const PROB: &str = "PROB";
-pub const LANG: &str = "LANG";
-
-pub const ADJ: &str = "ADJ";
-pub const ADP: &str = "ADP";
-pub const ADV: &str = "ADV";
-pub const AUX: &str = "AUX";
-pub const CONJ: &str = "CONJ";
-pub const CCONJ: &str = "CCONJ";
-pub const DET: &str = "DET";
-pub const INTJ: &str = "INTJ";
-pub const NOUN: &str = "NOUN";
-pub const NUM: &str = "NUM";
-pub const PART: &str = "PART";
-pub const PRON: &str = "PRON";
-pub const PROPN: &str = "PROPN";
-pub const PUNCT: &str = "PUNCT";
-pub const SCONJ: &str = "SCONJ";
-pub const SYM: &str = "SYM";
-pub const VERB: &str = "VERB";
-pub const X: &str = "X";
-pub const EOL: &str = "EOL";
-pub const SPACE: &str = "SPACE";
-
-// --- NER/entity and other tags as needed, e.g.:
-pub const PERSON: &str = "PERSON";
-pub const ORG: &str = "ORG";
-pub const LOC: &str = "LOC";
-// (add more for full coverage as needed)
-
-// ------ The enum definition ------
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-#[repr(u16)]
-pub enum Symbol {
-    NIL = 0,
-    IS_ALPHA,
-    IS_ASCII,
-    IS_DIGIT,
-    IS_LOWER,
-    IS_PUNCT,
-    IS_SPACE,
-    IS_TITLE,
-    IS_UPPER,
-    LIKE_URL,
-    LIKE_NUM,
-    LIKE_EMAIL,
-    IS_STOP,
-    IS_OOV_DEPRECATED,
-    IS_BRACKET,
-    IS_QUOTE,
-    IS_LEFT_PUNCT,
-    IS_RIGHT_PUNCT,
-    IS_CURRENCY,
+// Variant names are spaCy's attribute names (`IS_PUNCT`, `nsubj`), not Rust-style.
+#![allow(non_camel_case_types)]
 
-    FLAG19,
-    FLAG20,
-    FLAG21,
-    FLAG22,
-    FLAG23,
-    FLAG24,
-    FLAG25,
-    FLAG26,
-    FLAG27,
-    FLAG28,
-    FLAG29,
-    FLAG30,
-    FLAG31,
-    FLAG32,
-    FLAG33,
-    FLAG34,
-    FLAG35,
-    FLAG36,
-    FLAG37,
-    FLAG38,
-    FLAG39,
-    FLAG40,
-    FLAG41,
-    FLAG42,
-    FLAG43,
-    FLAG44,
-    FLAG45,
-    FLAG46,
-    FLAG47,
-    FLAG48,
-    FLAG49,
-    FLAG50,
-    FLAG51,
-    FLAG52,
-    FLAG53,
-    FLAG54,
-    FLAG55,
-    FLAG56,
-    FLAG57,
-    FLAG58,
-    FLAG59,
-    FLAG60,
-    FLAG61,
-    FLAG62,
-    FLAG63,
+use std::fmt;
+use std::str::FromStr;
 
-    ID,
-    ORTH,
-    LOWER,
-    NORM,
-    SHAPE,
-    PREFIX,
-    SUFFIX,
-    LENGTH,
-    CLUSTER,
-    LEMMA,
-    POS,
-    TAG,
-    DEP,
-    ENT_IOB,
-    ENT_TYPE,
-    HEAD,
-    SENT_START,
-    SPACY,
-    PROB,
-    LANG,
-    // --- POS/NER/Entity Tags ---
-    ADJ,
-    ADP,
-    ADV,
-    AUX,
-    CONJ,
-    CCONJ,
-    DET,
-    INTJ,
-    NOUN,
-    NUM,
-    PART,
-    PRON,
-    PROPN,
-    PUNCT,
-    SCONJ,
-    SYM,
-    VERB,
-    X,
-    EOL,
-    SPACE,
-
-    // --- (Example) NER tags, add as needed ---
-    PERSON,
-    NORP,
-    FACILITY,
-    ORG,
-    GPE,
-    LOC,
-    PRODUCT,
-    EVENT,
-    WORK_OF_ART,
-    LANGUAGE,
-    LAW,
-
-    DATE,
-    TIME,
-    PERCENT,
-    MONEY,
-    QUANTITY,
-    ORDINAL,
-    CARDINAL,
-
-    acomp,
-    advcl,
-    advmod,
-    agent,
-    amod,
-    appos,
-    attr,
-    aux,
-    auxpass,
-    cc,
-    ccomp,
-    complm,
-    conj,
-    cop,
-    csubj,
-    csubjpass,
-    dep,
-    det,
-    dobj,
-    expl,
-    hmod,
-    hyph,
-    infmod,
-    intj,
-    iobj,
-    mark,
-    meta,
-    neg,
-    nmod,
-    nn,
-    npadvmod,
-    nsubj,
-    nsubjpass,
-    num,
-    number,
-    oprd,
-    obj,
-    obl,
-    parataxis,
-    partmod,
-    pcomp,
-    pobj,
-    poss,
-    possessive,
-    preconj,
-    prep,
-    prt,
-    punct,
-    quantmod,
-    rcmod,
-    relcl,
-    root,
-    xcomp,
+// String constants (export these for dict keys etc.)
+pub const NIL: &str = Symbol::NIL.as_str();
+pub const IS_ALPHA: &str = Symbol::IS_ALPHA.as_str();
+pub const IS_ASCII: &str = Symbol::IS_ASCII.as_str();
+pub const IS_DIGIT: &str = Symbol::IS_DIGIT.as_str();
+pub const IS_LOWER: &str = Symbol::IS_LOWER.as_str();
+pub const IS_PUNCT: &str = Symbol::IS_PUNCT.as_str();
+pub const IS_SPACE: &str = Symbol::IS_SPACE.as_str();
+pub const IS_TITLE: &str = Symbol::IS_TITLE.as_str();
+pub const IS_UPPER: &str = Symbol::IS_UPPER.as_str();
+pub const LIKE_URL: &str = Symbol::LIKE_URL.as_str();
+pub const LIKE_NUM: &str = Symbol::LIKE_NUM.as_str();
+pub const LIKE_EMAIL: &str = Symbol::LIKE_EMAIL.as_str();
+pub const IS_STOP: &str = Symbol::IS_STOP.as_str();
+pub const IS_OOV_DEPRECATED: &str = Symbol::IS_OOV_DEPRECATED.as_str();
+pub const IS_BRACKET: &str = Symbol::IS_BRACKET.as_str();
+pub const IS_QUOTE: &str = Symbol::IS_QUOTE.as_str();
+pub const IS_LEFT_PUNCT: &str = Symbol::IS_LEFT_PUNCT.as_str();
+pub const IS_RIGHT_PUNCT: &str = Symbol::IS_RIGHT_PUNCT.as_str();
+pub const IS_CURRENCY: &str = Symbol::IS_CURRENCY.as_str();
+pub const ID: &str = Symbol::ID.as_str();
+pub const ORTH: &str = Symbol::ORTH.as_str();
+pub const LOWER: &str = Symbol::LOWER.as_str();
+pub const NORM: &str = Symbol::NORM.as_str();
+pub const SHAPE: &str = Symbol::SHAPE.as_str();
+pub const PREFIX: &str = Symbol::PREFIX.as_str();
+pub const SUFFIX: &str = Symbol::SUFFIX.as_str();
+pub const LENGTH: &str = Symbol::LENGTH.as_str();
+pub const CLUSTER: &str = Symbol::CLUSTER.as_str();
+pub const LEMMA: &str = Symbol::LEMMA.as_str();
+pub const POS: &str = Symbol::POS.as_str();
+pub const TAG: &str = Symbol::TAG.as_str();
+pub const DEP: &str = Symbol::DEP.as_str();
+pub const ENT_IOB: &str = Symbol::ENT_IOB.as_str();
+pub const ENT_TYPE: &str = Symbol::ENT_TYPE.as_str();
+pub const HEAD: &str = Symbol::HEAD.as_str();
+pub const SENT_START: &str = Symbol::SENT_START.as_str();
+pub const SPACY: &str = Symbol::SPACY.as_str();
+pub const PROB: &str = Symbol::PROB.as_str();
+pub const LANG: &str = Symbol::LANG.as_str();
+pub const ADJ: &str = Symbol::ADJ.as_str();
+pub const ADP: &str = Symbol::ADP.as_str();
+pub const ADV: &str = Symbol::ADV.as_str();
+pub const AUX: &str = Symbol::AUX.as_str();
+pub const CONJ: &str = Symbol::CONJ.as_str();
+pub const CCONJ: &str = Symbol::CCONJ.as_str();
+pub const DET: &str = Symbol::DET.as_str();
+pub const INTJ: &str = Symbol::INTJ.as_str();
+pub const NOUN: &str = Symbol::NOUN.as_str();
+pub const NUM: &str = Symbol::NUM.as_str();
+pub const PART: &str = Symbol::PART.as_str();
+pub const PRON: &str = Symbol::PRON.as_str();
+pub const PROPN: &str = Symbol::PROPN.as_str();
+pub const PUNCT: &str = Symbol::PUNCT.as_str();
+pub const SCONJ: &str = Symbol::SCONJ.as_str();
+pub const SYM: &str = Symbol::SYM.as_str();
+pub const VERB: &str = Symbol::VERB.as_str();
+pub const X: &str = Symbol::X.as_str();
+pub const EOL: &str = Symbol::EOL.as_str();
+pub const SPACE: &str = Symbol::SPACE.as_str();
+pub const PERSON: &str = Symbol::PERSON.as_str();
+pub const ORG: &str = Symbol::ORG.as_str();
+pub const LOC: &str = Symbol::LOC.as_str();
 
-    acl,
+macro_rules! symbols {
+    ($($variant:ident = $id:literal => $name:literal,)*) => {
+        /// spaCy's symbols, with the ids of spaCy's `symbols.pxd` (also their `StringStore` ids).
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        #[repr(u16)]
+        pub enum Symbol {
+            $($variant = $id,)*
+        }
 
-    ENT_KB_ID,
-    MORPH,
-    ENT_ID,
+        /// Every symbol, in id order.
+        const ALL: &[Symbol] = &[$(Symbol::$variant,)*];
 
-    IDX,
-    PLACEHOLDER,
+        impl Symbol {
+            /// Convert a Symbol enum to its canonical string key (for dict keys, etc.)
+            pub const fn as_str(&self) -> &'static str {
+                match self {
+                    $(Symbol::$variant => $name,)*
+                }
+            }
 
-    // --- Massive deprecated block (expand as needed, showing a representative sample) ---
-    DEPRECATED001,
-    DEPRECATED002,
-    DEPRECATED003,
-    DEPRECATED004,
-    DEPRECATED005,
-    DEPRECATED006,
-    DEPRECATED007,
-    DEPRECATED008,
-    DEPRECATED009,
-    DEPRECATED010,
-    DEPRECATED011,
-    DEPRECATED012,
-    DEPRECATED013,
-    DEPRECATED014,
-    DEPRECATED015,
-    DEPRECATED016,
-    DEPRECATED017,
-    DEPRECATED018,
-    DEPRECATED019,
-    DEPRECATED020,
-    DEPRECATED021,
-    DEPRECATED022,
-    DEPRECATED023,
-    DEPRECATED024,
-    DEPRECATED025,
-    DEPRECATED026,
-    DEPRECATED027,
-    DEPRECATED028,
-    DEPRECATED029,
-    DEPRECATED030,
-    DEPRECATED031,
-    DEPRECATED032,
-    DEPRECATED033,
-    DEPRECATED034,
-    DEPRECATED035,
-    DEPRECATED036,
-    DEPRECATED037,
-    DEPRECATED038,
-    DEPRECATED039,
-    DEPRECATED040,
-    DEPRECATED041,
-    DEPRECATED042,
-    DEPRECATED043,
-    DEPRECATED044,
-    DEPRECATED045,
-    DEPRECATED046,
-    DEPRECATED047,
-    DEPRECATED048,
-    DEPRECATED049,
-    DEPRECATED050,
-    DEPRECATED051,
-    DEPRECATED052,
-    DEPRECATED053,
-    DEPRECATED054,
-    DEPRECATED055,
-    DEPRECATED056,
-    DEPRECATED057,
-    DEPRECATED058,
-    DEPRECATED059,
-    DEPRECATED060,
-    DEPRECATED061,
-    DEPRECATED062,
-    DEPRECATED063,
-    DEPRECATED064,
-    DEPRECATED065,
-    DEPRECATED066,
-    DEPRECATED067,
-    DEPRECATED068,
-    DEPRECATED069,
-    DEPRECATED070,
-    DEPRECATED071,
-    DEPRECATED072,
-    DEPRECATED073,
-    DEPRECATED074,
-    DEPRECATED075,
-    DEPRECATED076,
-    DEPRECATED077,
-    DEPRECATED078,
-    DEPRECATED079,
-    DEPRECATED080,
-    DEPRECATED081,
-    DEPRECATED082,
-    DEPRECATED083,
-    DEPRECATED084,
-    DEPRECATED085,
-    DEPRECATED086,
-    DEPRECATED087,
-    DEPRECATED088,
-    DEPRECATED089,
-    DEPRECATED090,
-    DEPRECATED091,
-    DEPRECATED092,
-    DEPRECATED093,
-    DEPRECATED094,
-    DEPRECATED095,
-    DEPRECATED096,
-    DEPRECATED097,
-    DEPRECATED098,
-    DEPRECATED099,
-    DEPRECATED100,
-    DEPRECATED101,
-    DEPRECATED102,
-    DEPRECATED103,
-    DEPRECATED104,
-    DEPRECATED105,
-    DEPRECATED106,
-    DEPRECATED107,
-    DEPRECATED108,
-    DEPRECATED109,
-    DEPRECATED110,
-    DEPRECATED111,
-    DEPRECATED112,
-    DEPRECATED113,
-    DEPRECATED114,
-    DEPRECATED115,
-    DEPRECATED116,
-    DEPRECATED117,
-    DEPRECATED118,
-    DEPRECATED119,
-    DEPRECATED120,
-    DEPRECATED121,
-    DEPRECATED122,
-    DEPRECATED123,
-    DEPRECATED124,
-    DEPRECATED125,
-    DEPRECATED126,
-    DEPRECATED127,
-    DEPRECATED128,
-    DEPRECATED129,
-    DEPRECATED130,
-    DEPRECATED131,
-    DEPRECATED132,
-    DEPRECATED133,
-    DEPRECATED134,
-    DEPRECATED135,
-    DEPRECATED136,
-    DEPRECATED137,
-    DEPRECATED138,
-    DEPRECATED139,
-    DEPRECATED140,
-    DEPRECATED141,
-    DEPRECATED142,
-    DEPRECATED143,
-    DEPRECATED144,
-    DEPRECATED145,
-    DEPRECATED146,
-    DEPRECATED147,
-    DEPRECATED148,
-    DEPRECATED149,
-    DEPRECATED150,
-    DEPRECATED151,
-    DEPRECATED152,
-    DEPRECATED153,
-    DEPRECATED154,
-    DEPRECATED155,
-    DEPRECATED156,
-    DEPRECATED157,
-    DEPRECATED158,
-    DEPRECATED159,
-    DEPRECATED160,
-    DEPRECATED161,
-    DEPRECATED162,
-    DEPRECATED163,
-    DEPRECATED164,
-    DEPRECATED165,
-    DEPRECATED166,
-    DEPRECATED167,
-    DEPRECATED168,
-    DEPRECATED169,
-    DEPRECATED170,
-    DEPRECATED171,
-    DEPRECATED172,
-    DEPRECATED173,
-    DEPRECATED174,
-    DEPRECATED175,
-    DEPRECATED176,
-    DEPRECATED177,
-    DEPRECATED178,
-    DEPRECATED179,
-    DEPRECATED180,
-    DEPRECATED181,
-    DEPRECATED182,
-    DEPRECATED183,
-    DEPRECATED184,
-    DEPRECATED185,
-    DEPRECATED186,
-    DEPRECATED187,
-    DEPRECATED188,
-    DEPRECATED189,
-    DEPRECATED190,
-    DEPRECATED191,
-    DEPRECATED192,
-    DEPRECATED193,
-    DEPRECATED194,
-    DEPRECATED195,
-    DEPRECATED196,
-    DEPRECATED197,
-    DEPRECATED198,
-    DEPRECATED199,
-    DEPRECATED200,
-    DEPRECATED201,
-    DEPRECATED202,
-    DEPRECATED203,
-    DEPRECATED204,
-    DEPRECATED205,
-    DEPRECATED206,
-    DEPRECATED207,
-    DEPRECATED208,
-    DEPRECATED209,
-    DEPRECATED210,
-    DEPRECATED211,
-    DEPRECATED212,
-    DEPRECATED213,
-    DEPRECATED214,
-    DEPRECATED215,
-    DEPRECATED216,
-    DEPRECATED217,
-    DEPRECATED218,
-    DEPRECATED219,
-    DEPRECATED220,
-    DEPRECATED221,
-    DEPRECATED222,
-    DEPRECATED223,
-    DEPRECATED224,
-    DEPRECATED225,
-    DEPRECATED226,
-    DEPRECATED227,
-    DEPRECATED228,
-    DEPRECATED229,
-    DEPRECATED230,
-    DEPRECATED231,
-    DEPRECATED232,
-    DEPRECATED233,
-    DEPRECATED234,
-    DEPRECATED235,
-    DEPRECATED236,
-    DEPRECATED237,
-    DEPRECATED238,
-    DEPRECATED239,
-    DEPRECATED240,
-    DEPRECATED241,
-    DEPRECATED242,
-    DEPRECATED243,
-    DEPRECATED244,
-    DEPRECATED245,
-    DEPRECATED246,
-    DEPRECATED247,
-    DEPRECATED248,
-    DEPRECATED249,
-    DEPRECATED250,
-    DEPRECATED251,
-    DEPRECATED252,
-    DEPRECATED253,
-    DEPRECATED254,
-    DEPRECATED255,
-    DEPRECATED256,
-    DEPRECATED257,
-    DEPRECATED258,
-    DEPRECATED259,
-    DEPRECATED260,
-    DEPRECATED261,
-    DEPRECATED262,
-    DEPRECATED263,
-    DEPRECATED264,
-    DEPRECATED265,
-    DEPRECATED266,
-    DEPRECATED267,
-    DEPRECATED268,
-    DEPRECATED269,
-    DEPRECATED270,
-    DEPRECATED271,
-    DEPRECATED272,
-    DEPRECATED273,
-    DEPRECATED274,
-    DEPRECATED275,
-    DEPRECATED276,
+            /// The symbol named `s`, if any.
+            #[allow(clippy::should_implement_trait)]
+            pub fn from_str(s: &str) -> Option<Self> {
+                match s {
+                    $($name => Some(Symbol::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
 }
-impl Symbol {
-    /// Convert a Symbol enum to its canonical string key (for dict keys, etc.)
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Symbol::NIL => "NIL",
-            Symbol::IS_ALPHA => "IS_ALPHA",
-            Symbol::IS_ASCII => "IS_ASCII",
-            Symbol::IS_DIGIT => "IS_DIGIT",
-            Symbol::IS_LOWER => "IS_LOWER",
-            Symbol::IS_PUNCT => "IS_PUNCT",
-            Symbol::IS_SPACE => "IS_SPACE",
-            Symbol::IS_TITLE => "IS_TITLE",
-            Symbol::IS_UPPER => "IS_UPPER",
-            Symbol::LIKE_URL => "LIKE_URL",
-            Symbol::LIKE_NUM => "LIKE_NUM",
-            Symbol::LIKE_EMAIL => "LIKE_EMAIL",
-            Symbol::IS_STOP => "IS_STOP",
-            Symbol::IS_OOV_DEPRECATED => "IS_OOV_DEPRECATED",
-            Symbol::IS_BRACKET => "IS_BRACKET",
-            Symbol::IS_QUOTE => "IS_QUOTE",
-            Symbol::IS_LEFT_PUNCT => "IS_LEFT_PUNCT",
-            Symbol::IS_RIGHT_PUNCT => "IS_RIGHT_PUNCT",
-            Symbol::IS_CURRENCY => "IS_CURRENCY",
-            Symbol::ID => "ID",
-            Symbol::ORTH => "ORTH",
-            Symbol::LOWER => "LOWER",
-            Symbol::NORM => "NORM",
-            Symbol::SHAPE => "SHAPE",
-            Symbol::PREFIX => "PREFIX",
-            Symbol::SUFFIX => "SUFFIX",
-            Symbol::LENGTH => "LENGTH",
-            Symbol::CLUSTER => "CLUSTER",
-            Symbol::LEMMA => "LEMMA",
-            Symbol::POS => "POS",
-            Symbol::TAG => "TAG",
-            Symbol::DEP => "DEP",
-            Symbol::ENT_IOB => "ENT_IOB",
-            Symbol::ENT_TYPE => "ENT_TYPE",
-            Symbol::HEAD => "HEAD",
-            Symbol::SENT_START => "SENT_START",
-            Symbol::SPACY => "SPACY",
-            Symbol::PROB => "PROB",
-            Symbol::LANG => "LANG",
-            Symbol::ADJ => "ADJ",
-            Symbol::ADP => "ADP",
-            Symbol::ADV => "ADV",
-            Symbol::AUX => "AUX",
-            Symbol::CONJ => "CONJ",
-            Symbol::CCONJ => "CCONJ",
-            Symbol::DET => "DET",
-            Symbol::INTJ => "INTJ",
-            Symbol::NOUN => "NOUN",
-            Symbol::NUM => "NUM",
-            Symbol::PART => "PART",
-            Symbol::PRON => "PRON",
-            Symbol::PROPN => "PROPN",
-            Symbol::PUNCT => "PUNCT",
-            Symbol::SCONJ => "SCONJ",
-            Symbol::SYM => "SYM",
-            Symbol::VERB => "VERB",
-            Symbol::X => "X",
-            Symbol::EOL => "EOL",
-            Symbol::SPACE => "SPACE",
-
-            // --- Entity types ---
-            Symbol::PERSON => "PERSON",
-            Symbol::NORP => "NORP",
-            Symbol::FACILITY => "FACILITY",
-            Symbol::ORG => "ORG",
-            Symbol::GPE => "GPE",
-            Symbol::LOC => "LOC",
-            Symbol::PRODUCT => "PRODUCT",
-            Symbol::EVENT => "EVENT",
-            Symbol::WORK_OF_ART => "WORK_OF_ART",
-            Symbol::LANGUAGE => "LANGUAGE",
-            Symbol::LAW => "LAW",
-
-            Symbol::DATE => "DATE",
-            Symbol::TIME => "TIME",
-            Symbol::PERCENT => "PERCENT",
-            Symbol::MONEY => "MONEY",
-            Symbol::QUANTITY => "QUANTITY",
-            Symbol::ORDINAL => "ORDINAL",
-            Symbol::CARDINAL => "CARDINAL",
 
-            // --- Syntactic dependency labels (sample) ---
-            Symbol::acomp => "acomp",
-            Symbol::advcl => "advcl",
-            Symbol::advmod => "advmod",
-            Symbol::agent => "agent",
-            Symbol::amod => "amod",
-            Symbol::appos => "appos",
-            Symbol::attr => "attr",
-            Symbol::aux => "aux",
-            Symbol::auxpass => "auxpass",
-            Symbol::cc => "cc",
-            Symbol::ccomp => "ccomp",
-            Symbol::complm => "complm",
-            Symbol::conj => "conj",
-            Symbol::cop => "cop",
-            Symbol::csubj => "csubj",
-            Symbol::csubjpass => "csubjpass",
-            Symbol::dep => "dep",
-            Symbol::det => "det",
-            Symbol::dobj => "dobj",
-            Symbol::expl => "expl",
-            Symbol::hmod => "hmod",
-            Symbol::hyph => "hyph",
-            Symbol::infmod => "infmod",
-            Symbol::intj => "intj",
-            Symbol::iobj => "iobj",
-            Symbol::mark => "mark",
-            Symbol::meta => "meta",
-            Symbol::neg => "neg",
-            Symbol::nmod => "nmod",
-            Symbol::nn => "nn",
-            Symbol::npadvmod => "npadvmod",
-            Symbol::nsubj => "nsubj",
-            Symbol::nsubjpass => "nsubjpass",
-            Symbol::num => "num",
-            Symbol::number => "number",
-            Symbol::oprd => "oprd",
-            Symbol::obj => "obj",
-            Symbol::obl => "obl",
-            Symbol::parataxis => "parataxis",
-            Symbol::partmod => "partmod",
-            Symbol::pcomp => "pcomp",
-            Symbol::pobj => "pobj",
-            Symbol::poss => "poss",
-            Symbol::possessive => "possessive",
-            Symbol::preconj => "preconj",
-            Symbol::prep => "prep",
-            Symbol::prt => "prt",
-            Symbol::punct => "punct",
-            Symbol::quantmod => "quantmod",
-            Symbol::rcmod => "rcmod",
-            Symbol::relcl => "relcl",
-            Symbol::root => "root",
-            Symbol::xcomp => "xcomp",
-
-            Symbol::acl => "acl",
-
-            Symbol::ENT_KB_ID => "ENT_KB_ID",
-            Symbol::MORPH => "MORPH",
-            Symbol::ENT_ID => "ENT_ID",
-
-            Symbol::IDX => "IDX",
-            Symbol::_ => "_",
+symbols! {
+    // Lexeme flags.
+    NIL = 0 => "NIL",
+    IS_ALPHA = 1 => "IS_ALPHA",
+    IS_ASCII = 2 => "IS_ASCII",
+    IS_DIGIT = 3 => "IS_DIGIT",
+    IS_LOWER = 4 => "IS_LOWER",
+    IS_PUNCT = 5 => "IS_PUNCT",
+    IS_SPACE = 6 => "IS_SPACE",
+    IS_TITLE = 7 => "IS_TITLE",
+    IS_UPPER = 8 => "IS_UPPER",
+    LIKE_URL = 9 => "LIKE_URL",
+    LIKE_NUM = 10 => "LIKE_NUM",
+    LIKE_EMAIL = 11 => "LIKE_EMAIL",
+    IS_STOP = 12 => "IS_STOP",
+    IS_OOV_DEPRECATED = 13 => "IS_OOV_DEPRECATED",
+    IS_BRACKET = 14 => "IS_BRACKET",
+    IS_QUOTE = 15 => "IS_QUOTE",
+    IS_LEFT_PUNCT = 16 => "IS_LEFT_PUNCT",
+    IS_RIGHT_PUNCT = 17 => "IS_RIGHT_PUNCT",
+    IS_CURRENCY = 18 => "IS_CURRENCY",
+    FLAG19 = 19 => "FLAG19",
+    FLAG20 = 20 => "FLAG20",
+    FLAG21 = 21 => "FLAG21",
+    FLAG22 = 22 => "FLAG22",
+    FLAG23 = 23 => "FLAG23",
+    FLAG24 = 24 => "FLAG24",
+    FLAG25 = 25 => "FLAG25",
+    FLAG26 = 26 => "FLAG26",
+    FLAG27 = 27 => "FLAG27",
+    FLAG28 = 28 => "FLAG28",
+    FLAG29 = 29 => "FLAG29",
+    FLAG30 = 30 => "FLAG30",
+    FLAG31 = 31 => "FLAG31",
+    FLAG32 = 32 => "FLAG32",
+    FLAG33 = 33 => "FLAG33",
+    FLAG34 = 34 => "FLAG34",
+    FLAG35 = 35 => "FLAG35",
+    FLAG36 = 36 => "FLAG36",
+    FLAG37 = 37 => "FLAG37",
+    FLAG38 = 38 => "FLAG38",
+    FLAG39 = 39 => "FLAG39",
+    FLAG40 = 40 => "FLAG40",
+    FLAG41 = 41 => "FLAG41",
+    FLAG42 = 42 => "FLAG42",
+    FLAG43 = 43 => "FLAG43",
+    FLAG44 = 44 => "FLAG44",
+    FLAG45 = 45 => "FLAG45",
+    FLAG46 = 46 => "FLAG46",
+    FLAG47 = 47 => "FLAG47",
+    FLAG48 = 48 => "FLAG48",
+    FLAG49 = 49 => "FLAG49",
+    FLAG50 = 50 => "FLAG50",
+    FLAG51 = 51 => "FLAG51",
+    FLAG52 = 52 => "FLAG52",
+    FLAG53 = 53 => "FLAG53",
+    FLAG54 = 54 => "FLAG54",
+    FLAG55 = 55 => "FLAG55",
+    FLAG56 = 56 => "FLAG56",
+    FLAG57 = 57 => "FLAG57",
+    FLAG58 = 58 => "FLAG58",
+    FLAG59 = 59 => "FLAG59",
+    FLAG60 = 60 => "FLAG60",
+    FLAG61 = 61 => "FLAG61",
+    FLAG62 = 62 => "FLAG62",
+    FLAG63 = 63 => "FLAG63",
+    // Token attributes.
+    ID = 64 => "ID",
+    ORTH = 65 => "ORTH",
+    LOWER = 66 => "LOWER",
+    NORM = 67 => "NORM",
+    SHAPE = 68 => "SHAPE",
+    PREFIX = 69 => "PREFIX",
+    SUFFIX = 70 => "SUFFIX",
+    LENGTH = 71 => "LENGTH",
+    CLUSTER = 72 => "CLUSTER",
+    LEMMA = 73 => "LEMMA",
+    POS = 74 => "POS",
+    TAG = 75 => "TAG",
+    DEP = 76 => "DEP",
+    ENT_IOB = 77 => "ENT_IOB",
+    ENT_TYPE = 78 => "ENT_TYPE",
+    HEAD = 79 => "HEAD",
+    SENT_START = 80 => "SENT_START",
+    SPACY = 81 => "SPACY",
+    PROB = 82 => "PROB",
+    LANG = 83 => "LANG",
+    // Universal part-of-speech tags.
+    ADJ = 84 => "ADJ",
+    ADP = 85 => "ADP",
+    ADV = 86 => "ADV",
+    AUX = 87 => "AUX",
+    CONJ = 88 => "CONJ",
+    CCONJ = 89 => "CCONJ",
+    DET = 90 => "DET",
+    INTJ = 91 => "INTJ",
+    NOUN = 92 => "NOUN",
+    NUM = 93 => "NUM",
+    PART = 94 => "PART",
+    PRON = 95 => "PRON",
+    PROPN = 96 => "PROPN",
+    PUNCT = 97 => "PUNCT",
+    SCONJ = 98 => "SCONJ",
+    SYM = 99 => "SYM",
+    VERB = 100 => "VERB",
+    X = 101 => "X",
+    EOL = 102 => "EOL",
+    SPACE = 103 => "SPACE",
+    // Retired ids, kept so the later ones do not move.
+    DEPRECATED001 = 104 => "DEPRECATED001",
+    DEPRECATED002 = 105 => "DEPRECATED002",
+    DEPRECATED003 = 106 => "DEPRECATED003",
+    DEPRECATED004 = 107 => "DEPRECATED004",
+    DEPRECATED005 = 108 => "DEPRECATED005",
+    DEPRECATED006 = 109 => "DEPRECATED006",
+    DEPRECATED007 = 110 => "DEPRECATED007",
+    DEPRECATED008 = 111 => "DEPRECATED008",
+    DEPRECATED009 = 112 => "DEPRECATED009",
+    DEPRECATED010 = 113 => "DEPRECATED010",
+    DEPRECATED011 = 114 => "DEPRECATED011",
+    DEPRECATED012 = 115 => "DEPRECATED012",
+    DEPRECATED013 = 116 => "DEPRECATED013",
+    DEPRECATED014 = 117 => "DEPRECATED014",
+    DEPRECATED015 = 118 => "DEPRECATED015",
+    DEPRECATED016 = 119 => "DEPRECATED016",
+    DEPRECATED017 = 120 => "DEPRECATED017",
+    DEPRECATED018 = 121 => "DEPRECATED018",
+    DEPRECATED019 = 122 => "DEPRECATED019",
+    DEPRECATED020 = 123 => "DEPRECATED020",
+    DEPRECATED021 = 124 => "DEPRECATED021",
+    DEPRECATED022 = 125 => "DEPRECATED022",
+    DEPRECATED023 = 126 => "DEPRECATED023",
+    DEPRECATED024 = 127 => "DEPRECATED024",
+    DEPRECATED025 = 128 => "DEPRECATED025",
+    DEPRECATED026 = 129 => "DEPRECATED026",
+    DEPRECATED027 = 130 => "DEPRECATED027",
+    DEPRECATED028 = 131 => "DEPRECATED028",
+    DEPRECATED029 = 132 => "DEPRECATED029",
+    DEPRECATED030 = 133 => "DEPRECATED030",
+    DEPRECATED031 = 134 => "DEPRECATED031",
+    DEPRECATED032 = 135 => "DEPRECATED032",
+    DEPRECATED033 = 136 => "DEPRECATED033",
+    DEPRECATED034 = 137 => "DEPRECATED034",
+    DEPRECATED035 = 138 => "DEPRECATED035",
+    DEPRECATED036 = 139 => "DEPRECATED036",
+    DEPRECATED037 = 140 => "DEPRECATED037",
+    DEPRECATED038 = 141 => "DEPRECATED038",
+    DEPRECATED039 = 142 => "DEPRECATED039",
+    DEPRECATED040 = 143 => "DEPRECATED040",
+    DEPRECATED041 = 144 => "DEPRECATED041",
+    DEPRECATED042 = 145 => "DEPRECATED042",
+    DEPRECATED043 = 146 => "DEPRECATED043",
+    DEPRECATED044 = 147 => "DEPRECATED044",
+    DEPRECATED045 = 148 => "DEPRECATED045",
+    DEPRECATED046 = 149 => "DEPRECATED046",
+    DEPRECATED047 = 150 => "DEPRECATED047",
+    DEPRECATED048 = 151 => "DEPRECATED048",
+    DEPRECATED049 = 152 => "DEPRECATED049",
+    DEPRECATED050 = 153 => "DEPRECATED050",
+    DEPRECATED051 = 154 => "DEPRECATED051",
+    DEPRECATED052 = 155 => "DEPRECATED052",
+    DEPRECATED053 = 156 => "DEPRECATED053",
+    DEPRECATED054 = 157 => "DEPRECATED054",
+    DEPRECATED055 = 158 => "DEPRECATED055",
+    DEPRECATED056 = 159 => "DEPRECATED056",
+    DEPRECATED057 = 160 => "DEPRECATED057",
+    DEPRECATED058 = 161 => "DEPRECATED058",
+    DEPRECATED059 = 162 => "DEPRECATED059",
+    DEPRECATED060 = 163 => "DEPRECATED060",
+    DEPRECATED061 = 164 => "DEPRECATED061",
+    DEPRECATED062 = 165 => "DEPRECATED062",
+    DEPRECATED063 = 166 => "DEPRECATED063",
+    DEPRECATED064 = 167 => "DEPRECATED064",
+    DEPRECATED065 = 168 => "DEPRECATED065",
+    DEPRECATED066 = 169 => "DEPRECATED066",
+    DEPRECATED067 = 170 => "DEPRECATED067",
+    DEPRECATED068 = 171 => "DEPRECATED068",
+    DEPRECATED069 = 172 => "DEPRECATED069",
+    DEPRECATED070 = 173 => "DEPRECATED070",
+    DEPRECATED071 = 174 => "DEPRECATED071",
+    DEPRECATED072 = 175 => "DEPRECATED072",
+    DEPRECATED073 = 176 => "DEPRECATED073",
+    DEPRECATED074 = 177 => "DEPRECATED074",
+    DEPRECATED075 = 178 => "DEPRECATED075",
+    DEPRECATED076 = 179 => "DEPRECATED076",
+    DEPRECATED077 = 180 => "DEPRECATED077",
+    DEPRECATED078 = 181 => "DEPRECATED078",
+    DEPRECATED079 = 182 => "DEPRECATED079",
+    DEPRECATED080 = 183 => "DEPRECATED080",
+    DEPRECATED081 = 184 => "DEPRECATED081",
+    DEPRECATED082 = 185 => "DEPRECATED082",
+    DEPRECATED083 = 186 => "DEPRECATED083",
+    DEPRECATED084 = 187 => "DEPRECATED084",
+    DEPRECATED085 = 188 => "DEPRECATED085",
+    DEPRECATED086 = 189 => "DEPRECATED086",
+    DEPRECATED087 = 190 => "DEPRECATED087",
+    DEPRECATED088 = 191 => "DEPRECATED088",
+    DEPRECATED089 = 192 => "DEPRECATED089",
+    DEPRECATED090 = 193 => "DEPRECATED090",
+    DEPRECATED091 = 194 => "DEPRECATED091",
+    DEPRECATED092 = 195 => "DEPRECATED092",
+    DEPRECATED093 = 196 => "DEPRECATED093",
+    DEPRECATED094 = 197 => "DEPRECATED094",
+    DEPRECATED095 = 198 => "DEPRECATED095",
+    DEPRECATED096 = 199 => "DEPRECATED096",
+    DEPRECATED097 = 200 => "DEPRECATED097",
+    DEPRECATED098 = 201 => "DEPRECATED098",
+    DEPRECATED099 = 202 => "DEPRECATED099",
+    DEPRECATED100 = 203 => "DEPRECATED100",
+    DEPRECATED101 = 204 => "DEPRECATED101",
+    DEPRECATED102 = 205 => "DEPRECATED102",
+    DEPRECATED103 = 206 => "DEPRECATED103",
+    DEPRECATED104 = 207 => "DEPRECATED104",
+    DEPRECATED105 = 208 => "DEPRECATED105",
+    DEPRECATED106 = 209 => "DEPRECATED106",
+    DEPRECATED107 = 210 => "DEPRECATED107",
+    DEPRECATED108 = 211 => "DEPRECATED108",
+    DEPRECATED109 = 212 => "DEPRECATED109",
+    DEPRECATED110 = 213 => "DEPRECATED110",
+    DEPRECATED111 = 214 => "DEPRECATED111",
+    DEPRECATED112 = 215 => "DEPRECATED112",
+    DEPRECATED113 = 216 => "DEPRECATED113",
+    DEPRECATED114 = 217 => "DEPRECATED114",
+    DEPRECATED115 = 218 => "DEPRECATED115",
+    DEPRECATED116 = 219 => "DEPRECATED116",
+    DEPRECATED117 = 220 => "DEPRECATED117",
+    DEPRECATED118 = 221 => "DEPRECATED118",
+    DEPRECATED119 = 222 => "DEPRECATED119",
+    DEPRECATED120 = 223 => "DEPRECATED120",
+    DEPRECATED121 = 224 => "DEPRECATED121",
+    DEPRECATED122 = 225 => "DEPRECATED122",
+    DEPRECATED123 = 226 => "DEPRECATED123",
+    DEPRECATED124 = 227 => "DEPRECATED124",
+    DEPRECATED125 = 228 => "DEPRECATED125",
+    DEPRECATED126 = 229 => "DEPRECATED126",
+    DEPRECATED127 = 230 => "DEPRECATED127",
+    DEPRECATED128 = 231 => "DEPRECATED128",
+    DEPRECATED129 = 232 => "DEPRECATED129",
+    DEPRECATED130 = 233 => "DEPRECATED130",
+    DEPRECATED131 = 234 => "DEPRECATED131",
+    DEPRECATED132 = 235 => "DEPRECATED132",
+    DEPRECATED133 = 236 => "DEPRECATED133",
+    DEPRECATED134 = 237 => "DEPRECATED134",
+    DEPRECATED135 = 238 => "DEPRECATED135",
+    DEPRECATED136 = 239 => "DEPRECATED136",
+    DEPRECATED137 = 240 => "DEPRECATED137",
+    DEPRECATED138 = 241 => "DEPRECATED138",
+    DEPRECATED139 = 242 => "DEPRECATED139",
+    DEPRECATED140 = 243 => "DEPRECATED140",
+    DEPRECATED141 = 244 => "DEPRECATED141",
+    DEPRECATED142 = 245 => "DEPRECATED142",
+    DEPRECATED143 = 246 => "DEPRECATED143",
+    DEPRECATED144 = 247 => "DEPRECATED144",
+    DEPRECATED145 = 248 => "DEPRECATED145",
+    DEPRECATED146 = 249 => "DEPRECATED146",
+    DEPRECATED147 = 250 => "DEPRECATED147",
+    DEPRECATED148 = 251 => "DEPRECATED148",
+    DEPRECATED149 = 252 => "DEPRECATED149",
+    DEPRECATED150 = 253 => "DEPRECATED150",
+    DEPRECATED151 = 254 => "DEPRECATED151",
+    DEPRECATED152 = 255 => "DEPRECATED152",
+    DEPRECATED153 = 256 => "DEPRECATED153",
+    DEPRECATED154 = 257 => "DEPRECATED154",
+    DEPRECATED155 = 258 => "DEPRECATED155",
+    DEPRECATED156 = 259 => "DEPRECATED156",
+    DEPRECATED157 = 260 => "DEPRECATED157",
+    DEPRECATED158 = 261 => "DEPRECATED158",
+    DEPRECATED159 = 262 => "DEPRECATED159",
+    DEPRECATED160 = 263 => "DEPRECATED160",
+    DEPRECATED161 = 264 => "DEPRECATED161",
+    DEPRECATED162 = 265 => "DEPRECATED162",
+    DEPRECATED163 = 266 => "DEPRECATED163",
+    DEPRECATED164 = 267 => "DEPRECATED164",
+    DEPRECATED165 = 268 => "DEPRECATED165",
+    DEPRECATED166 = 269 => "DEPRECATED166",
+    DEPRECATED167 = 270 => "DEPRECATED167",
+    DEPRECATED168 = 271 => "DEPRECATED168",
+    DEPRECATED169 = 272 => "DEPRECATED169",
+    DEPRECATED170 = 273 => "DEPRECATED170",
+    DEPRECATED171 = 274 => "DEPRECATED171",
+    DEPRECATED172 = 275 => "DEPRECATED172",
+    DEPRECATED173 = 276 => "DEPRECATED173",
+    DEPRECATED174 = 277 => "DEPRECATED174",
+    DEPRECATED175 = 278 => "DEPRECATED175",
+    DEPRECATED176 = 279 => "DEPRECATED176",
+    DEPRECATED177 = 280 => "DEPRECATED177",
+    DEPRECATED178 = 281 => "DEPRECATED178",
+    DEPRECATED179 = 282 => "DEPRECATED179",
+    DEPRECATED180 = 283 => "DEPRECATED180",
+    DEPRECATED181 = 284 => "DEPRECATED181",
+    DEPRECATED182 = 285 => "DEPRECATED182",
+    DEPRECATED183 = 286 => "DEPRECATED183",
+    DEPRECATED184 = 287 => "DEPRECATED184",
+    DEPRECATED185 = 288 => "DEPRECATED185",
+    DEPRECATED186 = 289 => "DEPRECATED186",
+    DEPRECATED187 = 290 => "DEPRECATED187",
+    DEPRECATED188 = 291 => "DEPRECATED188",
+    DEPRECATED189 = 292 => "DEPRECATED189",
+    DEPRECATED190 = 293 => "DEPRECATED190",
+    DEPRECATED191 = 294 => "DEPRECATED191",
+    DEPRECATED192 = 295 => "DEPRECATED192",
+    DEPRECATED193 = 296 => "DEPRECATED193",
+    DEPRECATED194 = 297 => "DEPRECATED194",
+    DEPRECATED195 = 298 => "DEPRECATED195",
+    DEPRECATED196 = 299 => "DEPRECATED196",
+    DEPRECATED197 = 300 => "DEPRECATED197",
+    DEPRECATED198 = 301 => "DEPRECATED198",
+    DEPRECATED199 = 302 => "DEPRECATED199",
+    DEPRECATED200 = 303 => "DEPRECATED200",
+    DEPRECATED201 = 304 => "DEPRECATED201",
+    DEPRECATED202 = 305 => "DEPRECATED202",
+    DEPRECATED203 = 306 => "DEPRECATED203",
+    DEPRECATED204 = 307 => "DEPRECATED204",
+    DEPRECATED205 = 308 => "DEPRECATED205",
+    DEPRECATED206 = 309 => "DEPRECATED206",
+    DEPRECATED207 = 310 => "DEPRECATED207",
+    DEPRECATED208 = 311 => "DEPRECATED208",
+    DEPRECATED209 = 312 => "DEPRECATED209",
+    DEPRECATED210 = 313 => "DEPRECATED210",
+    DEPRECATED211 = 314 => "DEPRECATED211",
+    DEPRECATED212 = 315 => "DEPRECATED212",
+    DEPRECATED213 = 316 => "DEPRECATED213",
+    DEPRECATED214 = 317 => "DEPRECATED214",
+    DEPRECATED215 = 318 => "DEPRECATED215",
+    DEPRECATED216 = 319 => "DEPRECATED216",
+    DEPRECATED217 = 320 => "DEPRECATED217",
+    DEPRECATED218 = 321 => "DEPRECATED218",
+    DEPRECATED219 = 322 => "DEPRECATED219",
+    DEPRECATED220 = 323 => "DEPRECATED220",
+    DEPRECATED221 = 324 => "DEPRECATED221",
+    DEPRECATED222 = 325 => "DEPRECATED222",
+    DEPRECATED223 = 326 => "DEPRECATED223",
+    DEPRECATED224 = 327 => "DEPRECATED224",
+    DEPRECATED225 = 328 => "DEPRECATED225",
+    DEPRECATED226 = 329 => "DEPRECATED226",
+    DEPRECATED227 = 330 => "DEPRECATED227",
+    DEPRECATED228 = 331 => "DEPRECATED228",
+    DEPRECATED229 = 332 => "DEPRECATED229",
+    DEPRECATED230 = 333 => "DEPRECATED230",
+    DEPRECATED231 = 334 => "DEPRECATED231",
+    DEPRECATED232 = 335 => "DEPRECATED232",
+    DEPRECATED233 = 336 => "DEPRECATED233",
+    DEPRECATED234 = 337 => "DEPRECATED234",
+    DEPRECATED235 = 338 => "DEPRECATED235",
+    DEPRECATED236 = 339 => "DEPRECATED236",
+    DEPRECATED237 = 340 => "DEPRECATED237",
+    DEPRECATED238 = 341 => "DEPRECATED238",
+    DEPRECATED239 = 342 => "DEPRECATED239",
+    DEPRECATED240 = 343 => "DEPRECATED240",
+    DEPRECATED241 = 344 => "DEPRECATED241",
+    DEPRECATED242 = 345 => "DEPRECATED242",
+    DEPRECATED243 = 346 => "DEPRECATED243",
+    DEPRECATED244 = 347 => "DEPRECATED244",
+    DEPRECATED245 = 348 => "DEPRECATED245",
+    DEPRECATED246 = 349 => "DEPRECATED246",
+    DEPRECATED247 = 350 => "DEPRECATED247",
+    DEPRECATED248 = 351 => "DEPRECATED248",
+    DEPRECATED249 = 352 => "DEPRECATED249",
+    DEPRECATED250 = 353 => "DEPRECATED250",
+    DEPRECATED251 = 354 => "DEPRECATED251",
+    DEPRECATED252 = 355 => "DEPRECATED252",
+    DEPRECATED253 = 356 => "DEPRECATED253",
+    DEPRECATED254 = 357 => "DEPRECATED254",
+    DEPRECATED255 = 358 => "DEPRECATED255",
+    DEPRECATED256 = 359 => "DEPRECATED256",
+    DEPRECATED257 = 360 => "DEPRECATED257",
+    DEPRECATED258 = 361 => "DEPRECATED258",
+    DEPRECATED259 = 362 => "DEPRECATED259",
+    DEPRECATED260 = 363 => "DEPRECATED260",
+    DEPRECATED261 = 364 => "DEPRECATED261",
+    DEPRECATED262 = 365 => "DEPRECATED262",
+    DEPRECATED263 = 366 => "DEPRECATED263",
+    DEPRECATED264 = 367 => "DEPRECATED264",
+    DEPRECATED265 = 368 => "DEPRECATED265",
+    DEPRECATED266 = 369 => "DEPRECATED266",
+    DEPRECATED267 = 370 => "DEPRECATED267",
+    DEPRECATED268 = 371 => "DEPRECATED268",
+    DEPRECATED269 = 372 => "DEPRECATED269",
+    DEPRECATED270 = 373 => "DEPRECATED270",
+    DEPRECATED271 = 374 => "DEPRECATED271",
+    DEPRECATED272 = 375 => "DEPRECATED272",
+    DEPRECATED273 = 376 => "DEPRECATED273",
+    DEPRECATED274 = 377 => "DEPRECATED274",
+    DEPRECATED275 = 378 => "DEPRECATED275",
+    DEPRECATED276 = 379 => "DEPRECATED276",
+    // Entity labels.
+    PERSON = 380 => "PERSON",
+    NORP = 381 => "NORP",
+    FACILITY = 382 => "FACILITY",
+    ORG = 383 => "ORG",
+    GPE = 384 => "GPE",
+    LOC = 385 => "LOC",
+    PRODUCT = 386 => "PRODUCT",
+    EVENT = 387 => "EVENT",
+    WORK_OF_ART = 388 => "WORK_OF_ART",
+    LANGUAGE = 389 => "LANGUAGE",
+    LAW = 390 => "LAW",
+    DATE = 391 => "DATE",
+    TIME = 392 => "TIME",
+    PERCENT = 393 => "PERCENT",
+    MONEY = 394 => "MONEY",
+    QUANTITY = 395 => "QUANTITY",
+    ORDINAL = 396 => "ORDINAL",
+    CARDINAL = 397 => "CARDINAL",
+    // Dependency labels.
+    acomp = 398 => "acomp",
+    advcl = 399 => "advcl",
+    advmod = 400 => "advmod",
+    agent = 401 => "agent",
+    amod = 402 => "amod",
+    appos = 403 => "appos",
+    attr = 404 => "attr",
+    aux = 405 => "aux",
+    auxpass = 406 => "auxpass",
+    cc = 407 => "cc",
+    ccomp = 408 => "ccomp",
+    complm = 409 => "complm",
+    conj = 410 => "conj",
+    cop = 411 => "cop",
+    csubj = 412 => "csubj",
+    csubjpass = 413 => "csubjpass",
+    dep = 414 => "dep",
+    det = 415 => "det",
+    dobj = 416 => "dobj",
+    expl = 417 => "expl",
+    hmod = 418 => "hmod",
+    hyph = 419 => "hyph",
+    infmod = 420 => "infmod",
+    intj = 421 => "intj",
+    iobj = 422 => "iobj",
+    mark = 423 => "mark",
+    meta = 424 => "meta",
+    neg = 425 => "neg",
+    nmod = 426 => "nmod",
+    nn = 427 => "nn",
+    npadvmod = 428 => "npadvmod",
+    nsubj = 429 => "nsubj",
+    nsubjpass = 430 => "nsubjpass",
+    num = 431 => "num",
+    number = 432 => "number",
+    oprd = 433 => "oprd",
+    obj = 434 => "obj",
+    obl = 435 => "obl",
+    parataxis = 436 => "parataxis",
+    partmod = 437 => "partmod",
+    pcomp = 438 => "pcomp",
+    pobj = 439 => "pobj",
+    poss = 440 => "poss",
+    possessive = 441 => "possessive",
+    preconj = 442 => "preconj",
+    prep = 443 => "prep",
+    prt = 444 => "prt",
+    punct = 445 => "punct",
+    quantmod = 446 => "quantmod",
+    relcl = 447 => "relcl",
+    rcmod = 448 => "rcmod",
+    root = 449 => "root",
+    xcomp = 450 => "xcomp",
+    acl = 451 => "acl",
+    // Later additions.
+    ENT_KB_ID = 452 => "ENT_KB_ID",
+    MORPH = 453 => "MORPH",
+    ENT_ID = 454 => "ENT_ID",
+    IDX = 455 => "IDX",
+    PLACEHOLDER = 456 => "_",
+}
 
-            // --- DEPRECATED (showing sample; you would expand this list as in your enum) ---
-            Symbol::DEPRECATED001 => "DEPRECATED001",
-            Symbol::DEPRECATED002 => "DEPRECATED002",
-            Symbol::DEPRECATED003 => "DEPRECATED003",
-            // ...repeat for all DEPRECATEDxxx variants...
-            Symbol::DEPRECATED276 => "DEPRECATED276",
+// spaCy's ids are contiguous from 0, so a symbol's id is its index in `ALL`.
+const _: () = {
+    let mut i = 0;
+    while i < ALL.len() {
+        assert!(ALL[i] as usize == i, "symbol ids must be contiguous and in table order");
+        i += 1;
+    }
+};
 
-            // --- Flags (if you want to handle) ---
-            Symbol::FLAG19 => "FLAG19",
-            Symbol::FLAG20 => "FLAG20",
-            Symbol::FLAG21 => "FLAG21",
-            Symbol::FLAG22 => "FLAG22",
-            Symbol::FLAG23 => "FLAG23",
-            Symbol::FLAG24 => "FLAG24",
-            Symbol::FLAG25 => "FLAG25",
-            Symbol::FLAG26 => "FLAG26",
-            Symbol::FLAG27 => "FLAG27",
-            Symbol::FLAG28 => "FLAG28",
-            Symbol::FLAG29 => "FLAG29",
-            Symbol::FLAG30 => "FLAG30",
-            Symbol::FLAG31 => "FLAG31",
-            Symbol::FLAG32 => "FLAG32",
-            Symbol::FLAG33 => "FLAG33",
-            Symbol::FLAG34 => "FLAG34",
-            Symbol::FLAG35 => "FLAG35",
-            Symbol::FLAG36 => "FLAG36",
-            Symbol::FLAG37 => "FLAG37",
-            Symbol::FLAG38 => "FLAG38",
-            Symbol::FLAG39 => "FLAG39",
-            Symbol::FLAG40 => "FLAG40",
-            Symbol::FLAG41 => "FLAG41",
-            Symbol::FLAG42 => "FLAG42",
-            Symbol::FLAG43 => "FLAG43",
-            Symbol::FLAG44 => "FLAG44",
-            Symbol::FLAG45 => "FLAG45",
-            Symbol::FLAG46 => "FLAG46",
-            Symbol::FLAG47 => "FLAG47",
-            Symbol::FLAG48 => "FLAG48",
-            Symbol::FLAG49 => "FLAG49",
-            Symbol::FLAG50 => "FLAG50",
-            Symbol::FLAG51 => "FLAG51",
-            Symbol::FLAG52 => "FLAG52",
-            Symbol::FLAG53 => "FLAG53",
-            Symbol::FLAG54 => "FLAG54",
-            Symbol::FLAG55 => "FLAG55",
-            Symbol::FLAG56 => "FLAG56",
-            Symbol::FLAG57 => "FLAG57",
-            Symbol::FLAG58 => "FLAG58",
-            Symbol::FLAG59 => "FLAG59",
-            Symbol::FLAG60 => "FLAG60",
-            Symbol::FLAG61 => "FLAG61",
-            Symbol::FLAG62 => "FLAG62",
-            Symbol::FLAG63 => "FLAG63",
-        }
+impl Symbol {
+    /// The symbol's id, as in spaCy (also its `StringStore` id).
+    pub const fn id(self) -> u64 {
+        self as u64
     }
 
-    /// Convert from a string to the Symbol enum (if known)
-    pub fn from_str(s: &str) -> Option<Self> {
-        match s {
-            "NIL" => Some(Symbol::NIL),
-            "IS_ALPHA" => Some(Symbol::IS_ALPHA),
-            "IS_ASCII" => Some(Symbol::IS_ASCII),
-            "IS_DIGIT" => Some(Symbol::IS_DIGIT),
-            "IS_LOWER" => Some(Symbol::IS_LOWER),
-            "IS_PUNCT" => Some(Symbol::IS_PUNCT),
-            "IS_SPACE" => Some(Symbol::IS_SPACE),
-            "IS_TITLE" => Some(Symbol::IS_TITLE),
-            "IS_UPPER" => Some(Symbol::IS_UPPER),
-            "LIKE_URL" => Some(Symbol::LIKE_URL),
-            "LIKE_NUM" => Some(Symbol::LIKE_NUM),
-            "LIKE_EMAIL" => Some(Symbol::LIKE_EMAIL),
-            "IS_STOP" => Some(Symbol::IS_STOP),
-            "IS_OOV_DEPRECATED" => Some(Symbol::IS_OOV_DEPRECATED),
-            "IS_BRACKET" => Some(Symbol::IS_BRACKET),
-            "IS_QUOTE" => Some(Symbol::IS_QUOTE),
-            "IS_LEFT_PUNCT" => Some(Symbol::IS_LEFT_PUNCT),
-            "IS_RIGHT_PUNCT" => Some(Symbol::IS_RIGHT_PUNCT),
-            "IS_CURRENCY" => Some(Symbol::IS_CURRENCY),
-            "ID" => Some(Symbol::ID),
-            "ORTH" => Some(Symbol::ORTH),
-            "LOWER" => Some(Symbol::LOWER),
-            "NORM" => Some(Symbol::NORM),
-            "SHAPE" => Some(Symbol::SHAPE),
-            "PREFIX" => Some(Symbol::PREFIX),
-            "SUFFIX" => Some(Symbol::SUFFIX),
-            "LENGTH" => Some(Symbol::LENGTH),
-            "CLUSTER" => Some(Symbol::CLUSTER),
-            "LEMMA" => Some(Symbol::LEMMA),
-            "POS" => Some(Symbol::POS),
-            "TAG" => Some(Symbol::TAG),
-            "DEP" => Some(Symbol::DEP),
-            "ENT_IOB" => Some(Symbol::ENT_IOB),
-            "ENT_TYPE" => Some(Symbol::ENT_TYPE),
-            "HEAD" => Some(Symbol::HEAD),
-            "SENT_START" => Some(Symbol::SENT_START),
-            "SPACY" => Some(Symbol::SPACY),
-            "PROB" => Some(Symbol::PROB),
-            "LANG" => Some(Symbol::LANG),
-            "ADJ" => Some(Symbol::ADJ),
-            "ADP" => Some(Symbol::ADP),
-            "ADV" => Some(Symbol::ADV),
-            "AUX" => Some(Symbol::AUX),
-            "CONJ" => Some(Symbol::CONJ),
-            "CCONJ" => Some(Symbol::CCONJ),
-            "DET" => Some(Symbol::DET),
-            "INTJ" => Some(Symbol::INTJ),
-            "NOUN" => Some(Symbol::NOUN),
-            "NUM" => Some(Symbol::NUM),
-            "PART" => Some(Symbol::PART),
-            "PRON" => Some(Symbol::PRON),
-            "PROPN" => Some(Symbol::PROPN),
-            "PUNCT" => Some(Symbol::PUNCT),
-            "SCONJ" => Some(Symbol::SCONJ),
-            "SYM" => Some(Symbol::SYM),
-            "VERB" => Some(Symbol::VERB),
-            "X" => Some(Symbol::X),
-            "EOL" => Some(Symbol::EOL),
-            "SPACE" => Some(Symbol::SPACE),
+    /// The symbol with spaCy id `id`, if any.
+    pub fn from_id(id: u64) -> Option<Self> {
+        usize::try_from(id).ok().and_then(|i| ALL.get(i)).copied()
+    }
 
-            // --- Entities ---
-            "PERSON" => Some(Symbol::PERSON),
-            "NORP" => Some(Symbol::NORP),
-            "FACILITY" => Some(Symbol::FACILITY),
-            "ORG" => Some(Symbol::ORG),
-            "GPE" => Some(Symbol::GPE),
-            "LOC" => Some(Symbol::LOC),
-            "PRODUCT" => Some(Symbol::PRODUCT),
-            "EVENT" => Some(Symbol::EVENT),
-            "WORK_OF_ART" => Some(Symbol::WORK_OF_ART),
-            "LANGUAGE" => Some(Symbol::LANGUAGE),
-            "LAW" => Some(Symbol::LAW),
-            "DATE" => Some(Symbol::DATE),
-            "TIME" => Some(Symbol::TIME),
-            "PERCENT" => Some(Symbol::PERCENT),
-            "MONEY" => Some(Symbol::MONEY),
-            "QUANTITY" => Some(Symbol::QUANTITY),
-            "ORDINAL" => Some(Symbol::ORDINAL),
-            "CARDINAL" => Some(Symbol::CARDINAL),
-            // ...rest of dependencies/entities/flags/deprecated as in as_str...
-            _ => None,
-        }
+    /// All symbols, in id order.
+    pub fn iter() -> impl ExactSizeIterator<Item = Symbol> + Clone {
+        ALL.iter().copied()
     }
 }
-/// Return a vector of all canonical symbol names, in order.
-/// Like spaCy’s NAMES.
-pub fn all_symbol_names() -> Vec<&'static str> {
-    vec![
-        "NIL",
-        "IS_ALPHA",
-        "IS_ASCII",
-        "IS_DIGIT",
-        "IS_LOWER",
-        "IS_PUNCT",
-        "IS_SPACE",
-        "IS_TITLE",
-        "IS_UPPER",
-        "LIKE_URL",
-        "LIKE_NUM",
-        "LIKE_EMAIL",
-        "IS_STOP",
-        "IS_OOV_DEPRECATED",
-        "IS_BRACKET",
-        "IS_QUOTE",
-        "IS_LEFT_PUNCT",
-        "IS_RIGHT_PUNCT",
-        "IS_CURRENCY",
-        "FLAG19", "FLAG20", "FLAG21", "FLAG22", "FLAG23", "FLAG24", "FLAG25", "FLAG26",
-        "FLAG27", "FLAG28", "FLAG29", "FLAG30", "FLAG31", "FLAG32", "FLAG33", "FLAG34",
-        "FLAG35", "FLAG36", "FLAG37", "FLAG38", "FLAG39", "FLAG40", "FLAG41", "FLAG42",
-        "FLAG43", "FLAG44", "FLAG45", "FLAG46", "FLAG47", "FLAG48", "FLAG49", "FLAG50",
-        "FLAG51", "FLAG52", "FLAG53", "FLAG54", "FLAG55", "FLAG56", "FLAG57", "FLAG58",
-        "FLAG59", "FLAG60", "FLAG61", "FLAG62", "FLAG63",
-        "ID", "ORTH", "LOWER", "NORM", "SHAPE", "PREFIX", "SUFFIX",
-        "LENGTH", "CLUSTER", "LEMMA", "POS", "TAG", "DEP", "ENT_IOB", "ENT_TYPE",
-        "HEAD", "SENT_START", "SPACY", "PROB", "LANG",
-        "ADJ", "ADP", "ADV", "AUX", "CONJ", "CCONJ", "DET", "INTJ", "NOUN", "NUM",
-        "PART", "PRON", "PROPN", "PUNCT", "SCONJ", "SYM", "VERB", "X", "EOL", "SPACE",
-        "PERSON", "NORP", "FACILITY", "ORG", "GPE", "LOC", "PRODUCT", "EVENT",
-        "WORK_OF_ART", "LANGUAGE", "LAW",
-        "DATE", "TIME", "PERCENT", "MONEY", "QUANTITY", "ORDINAL", "CARDINAL",
-        "acomp", "advcl", "advmod", "agent", "amod", "appos", "attr", "aux", "auxpass",
-        "cc", "ccomp", "complm", "conj", "cop", "csubj", "csubjpass", "dep", "det",
-        "dobj", "expl", "hmod", "hyph", "infmod", "intj", "iobj", "mark", "meta",
-        "neg", "nmod", "nn", "npadvmod", "nsubj", "nsubjpass", "num", "number", "oprd",
-        "obj", "obl", "parataxis", "partmod", "pcomp", "pobj", "poss", "possessive",
-        "preconj", "prep", "prt", "punct", "quantmod", "rcmod", "relcl", "root",
-        "xcomp", "acl", "ENT_KB_ID", "MORPH", "ENT_ID", "IDX", "_",
-        // All DEPRECATED entries
-        "DEPRECATED001", "DEPRECATED002", "DEPRECATED003", /* ... */ "DEPRECATED276",
-    ]
-}
 
-// ------ Docs and usage ------
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
-/*
-    # Example Usage
+/// Error of parsing a string that is not a symbol name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseSymbolError {
+    pub name: String,
+}
 
-    use crate::symbols::{Symbol, all_symbol_names};
+impl fmt::Display for ParseSymbolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown symbol {:?}", self.name)
+    }
+}
 
-    fn main() {
-        // Convert from Symbol enum to string:
-        let s = Symbol::NOUN;
-        println!("Symbol for NOUN: {}", s.as_str());  // Output: "NOUN"
+impl std::error::Error for ParseSymbolError {}
 
-        // Convert from string to Symbol enum:
-        if let Some(symbol) = Symbol::from_str("NUM") {
-            println!("NUM as enum: {:?}", symbol);
-        }
+impl FromStr for Symbol {
+    type Err = ParseSymbolError;
 
-        // List all symbol names
-        let all = all_symbol_names();
-        println!("All symbols: {:?}", all);
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Symbol::from_str(s).ok_or_else(|| ParseSymbolError { name: s.to_string() })
     }
-*/
+}
 
-/// This module is auto-generated to closely match spaCy’s symbol tables in Python.
-/// Add or remove fields as needed for your own pipeline.
-/// This file is 100% safe to expand or script-generate for your needs.
-/// Author: (your name/project)
+/// Return a vector of all canonical symbol names, in order.
+/// Like spaCy’s NAMES: the name at index `i` is the symbol with id `i`.
+pub fn all_symbol_names() -> Vec<&'static str> {
+    Symbol::iter().map(|symbol| symbol.as_str()).collect()
+}
@@ -0,0 +1,158 @@
+// src/token.rs
+
+use std::ops::Range;
+
+use crate::lex_attrs::{AttrValue, LexAttrs};
+use crate::offsets::{self, OffsetUnit, TextPos};
+use crate::symbols::Symbol;
+
+/// A single token produced by the tokenizer, with its position in the source text.
+///
+/// Offsets are half-open (`start..end`) and absolute within the text passed to
+/// `Tokenizer::tokenize`, in bytes, chars, UTF-16 code units and grapheme clusters
+/// (see `OffsetUnit`); `span` picks one. No token boundary falls inside a grapheme cluster.
+///
+/// `whitespace_after` mirrors spaCy's `SPACY` attribute: it is `true` when the token is
+/// immediately followed by a single space (U+0020).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Token {
+    pub text: String,
+    pub start_char: usize,
+    pub end_char: usize,
+    pub start_byte: usize,
+    pub end_byte: usize,
+    pub start_utf16: usize,
+    pub end_utf16: usize,
+    pub start_grapheme: usize,
+    pub end_grapheme: usize,
+    pub whitespace_after: bool,
+}
+
+impl Token {
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The span of the token in the input, counted in `unit`.
+    pub fn span(&self, unit: OffsetUnit) -> Range<usize> {
+        offsets::span(self.start_pos(), self.end_pos(), unit)
+    }
+
+    /// The lexical attribute `name` of the token (`Symbol::IS_PUNCT`, `Symbol::SHAPE`, ...),
+    /// or `None` if it is not one (see `LexAttrs`).
+    pub fn get(&self, name: Symbol) -> Option<AttrValue<'_>> {
+        LexAttrs::default_table().get(&self.text, name)
+    }
+
+    fn start_pos(&self) -> TextPos {
+        TextPos { byte: self.start_byte, char: self.start_char, utf16: self.start_utf16, grapheme: self.start_grapheme }
+    }
+
+    fn end_pos(&self) -> TextPos {
+        TextPos { byte: self.end_byte, char: self.end_char, utf16: self.end_utf16, grapheme: self.end_grapheme }
+    }
+
+    /// The trailing whitespace of this token, as spaCy's `token.whitespace_`.
+    pub fn whitespace(&self) -> &'static str {
+        if self.whitespace_after { " " } else { "" }
+    }
+
+    /// The token text followed by its trailing whitespace, as spaCy's `token.text_with_ws`.
+    pub fn text_with_ws(&self) -> String {
+        format!("{}{}", self.text, self.whitespace())
+    }
+}
+
+/// A token borrowing its text from the tokenized input, from `Tokenizer::tokenize_borrowed`.
+///
+/// Same fields as `Token` without the allocation: every token, special cases included, is a
+/// slice of the input, because special-case `ORTH` values must concatenate to their key.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TokenRef<'a> {
+    pub text: &'a str,
+    pub start_char: usize,
+    pub end_char: usize,
+    pub start_byte: usize,
+    pub end_byte: usize,
+    pub start_utf16: usize,
+    pub end_utf16: usize,
+    pub start_grapheme: usize,
+    pub end_grapheme: usize,
+    pub whitespace_after: bool,
+}
+
+impl<'a> TokenRef<'a> {
+    pub(crate) fn new(text: &'a str, start: TextPos, end: TextPos, whitespace_after: bool) -> Self {
+        TokenRef {
+            text,
+            start_char: start.char,
+            end_char: end.char,
+            start_byte: start.byte,
+            end_byte: end.byte,
+            start_utf16: start.utf16,
+            end_utf16: end.utf16,
+            start_grapheme: start.grapheme,
+            end_grapheme: end.grapheme,
+            whitespace_after,
+        }
+    }
+
+    pub(crate) fn start_pos(&self) -> TextPos {
+        TextPos { byte: self.start_byte, char: self.start_char, utf16: self.start_utf16, grapheme: self.start_grapheme }
+    }
+
+    pub(crate) fn end_pos(&self) -> TextPos {
+        TextPos { byte: self.end_byte, char: self.end_char, utf16: self.end_utf16, grapheme: self.end_grapheme }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// The byte span of the token in the input.
+    pub fn byte_span(&self) -> Range<usize> {
+        self.start_byte..self.end_byte
+    }
+
+    /// The span of the token in the input, counted in `unit`.
+    pub fn span(&self, unit: OffsetUnit) -> Range<usize> {
+        offsets::span(self.start_pos(), self.end_pos(), unit)
+    }
+
+    /// The lexical attribute `name` of the token, as `Token::get`.
+    pub fn get(&self, name: Symbol) -> Option<AttrValue<'a>> {
+        LexAttrs::default_table().get(self.text, name)
+    }
+
+    /// The trailing whitespace of this token, as spaCy's `token.whitespace_`.
+    pub fn whitespace(&self) -> &'static str {
+        if self.whitespace_after { " " } else { "" }
+    }
+
+    /// The token text followed by its trailing whitespace, as spaCy's `token.text_with_ws`.
+    pub fn text_with_ws(&self) -> String {
+        format!("{}{}", self.text, self.whitespace())
+    }
+
+    /// Copies the token into an owned `Token`.
+    pub fn to_token(&self) -> Token {
+        Token {
+            text: self.text.to_string(),
+            start_char: self.start_char,
+            end_char: self.end_char,
+            start_byte: self.start_byte,
+            end_byte: self.end_byte,
+            start_utf16: self.start_utf16,
+            end_utf16: self.end_utf16,
+            start_grapheme: self.start_grapheme,
+            end_grapheme: self.end_grapheme,
+            whitespace_after: self.whitespace_after,
+        }
+    }
+}
+
+impl From<TokenRef<'_>> for Token {
+    fn from(token: TokenRef<'_>) -> Self {
+        token.to_token()
+    }
+}
@@ -0,0 +1,1052 @@
+// src/tokenizer.rs
+use std::io::BufRead;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc; // For Arc to share rules across threads
+
+// Add AhoCorasick imports
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
+
+// Add rayon for parallel processing
+use rayon::prelude::*; // Import parallel iterators
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+use crate::affix::AffixMatcher;
+use crate::cache::{CacheStats, ChunkCache, DEFAULT_CACHE_CAPACITY};
+use crate::classify::{self, ClassificationReport, LiteralSet, Route, RuleRegex};
+use crate::pattern::{self, ExceptionAttributeMap, ExceptionMap, NORM, ORTH};
+use crate::config::RulesConfig;
+use crate::doc::Doc;
+use crate::fastpath::PlainChunks;
+use crate::offsets::{self, TextPos};
+use crate::spacy_import::{import_spacy_tokenizer, ImportReport};
+use crate::stream::TokenStream;
+use crate::explain::{Explanation, TokenRule};
+use crate::error::{RegexErrorPolicy, RuleError, RuleKind, TokenizerError};
+use crate::token::{Token, TokenRef};
+use crate::trace::{ChunkTracer, TraceSink, TraceStage};
+
+/// Compiled tokenization rules (prefixes, suffixes, infixes, token/url matchers and
+/// special-case exceptions) shared by every `Tokenizer` call.
+#[derive(Clone)]
+pub struct TokenizerRules {
+    prefixes: AffixMatcher,
+    suffixes: AffixMatcher,
+    /// Regex infixes that need a regex engine, with their index in the infix rule list.
+    regex_infixes: Vec<(usize, RuleRegex)>,
+    /// Regex infixes that are plain literals (e.g. `\.{2}`), matched with Aho-Corasick.
+    literal_regex_infixes: Option<LiteralSet>,
+    num_regex_infixes: usize,
+    literal_infix_matcher: Option<AhoCorasick>,
+    token_match: Option<RuleRegex>,
+    url_match: Option<RuleRegex>,
+    exceptions: ExceptionMap,
+    classification: ClassificationReport,
+    /// Set when no rule can split a run of letters (see `fastpath`).
+    plain_chunks: Option<PlainChunks>,
+}
+
+impl TokenizerRules {
+    /// Builds the default English rule set from `pattern.rs`.
+    pub fn new() -> Result<Self, TokenizerError> {
+        Self::from_patterns(
+            &pattern::get_english_prefix_patterns().into_iter().map(String::from).collect::<Vec<_>>(),
+            &pattern::get_english_suffix_patterns(),
+            &pattern::get_english_regex_infix_patterns(),
+            &pattern::get_english_literal_infix_strings(),
+            pattern::get_english_token_match_pattern_str().as_deref(),
+            Some(&pattern::get_english_url_match_pattern_str()),
+            pattern::get_english_tokenizer_exceptions(),
+        )
+    }
+
+    /// Loads a rule set from a JSON or TOML rules file (see `RulesConfig`).
+    pub fn from_config<P: AsRef<Path>>(path: P) -> Result<Self, TokenizerError> {
+        RulesConfig::from_path(path)?.compile()
+    }
+
+    /// Imports the rules of a spaCy tokenizer saved with `nlp.tokenizer.to_disk()`.
+    ///
+    /// Patterns that could not be translated to `fancy_regex` are skipped and listed in the report.
+    pub fn from_spacy<P: AsRef<Path>>(path: P) -> Result<(Self, ImportReport), TokenizerError> {
+        let (config, report) = import_spacy_tokenizer(path)?;
+        Ok((config.compile()?, report))
+    }
+
+    /// Compiles a rule set from raw pattern strings.
+    ///
+    /// Returns `TokenizerError::InvalidPattern` naming the first pattern that fails to compile.
+    pub fn from_patterns(
+        prefix_patterns: &[String],
+        suffix_patterns: &[String],
+        regex_infix_patterns: &[String],
+        literal_infix_strings: &[String],
+        token_match_pattern: Option<&str>,
+        url_match_pattern: Option<&str>,
+        exceptions: ExceptionMap,
+    ) -> Result<Self, TokenizerError> {
+        let mut classification = ClassificationReport::default();
+        let prefixes = AffixMatcher::new(RuleKind::Prefix, prefix_patterns, &mut classification)?;
+        let suffixes = AffixMatcher::new(RuleKind::Suffix, suffix_patterns, &mut classification)?;
+
+        let mut regex_infixes = Vec::new();
+        let mut literal_regex_infix_strings = Vec::new();
+        for (i, infix_pattern) in regex_infix_patterns.iter().enumerate() {
+            match compile_pattern(RuleKind::Infix, i, infix_pattern, &mut classification)? {
+                (Route::Literal(strings), _) => literal_regex_infix_strings.push((i, strings)),
+                (_, re) => regex_infixes.push((i, re)),
+            }
+        }
+        let literal_regex_infixes = if literal_regex_infix_strings.is_empty() {
+            None
+        } else {
+            Some(LiteralSet::infix(&literal_regex_infix_strings)?)
+        };
+
+        let literal_infix_matcher = if !literal_infix_strings.is_empty() {
+            Some(
+                AhoCorasickBuilder::new()
+                    // LeftmostFirst is often what you want for tokenizers,
+                    // as it matches the first pattern defined if there are overlaps.
+                    // Combined with sorting `literal_infix_strings` by length descending,
+                    // it ensures longest matches are preferred if they start at the same point.
+                    .match_kind(MatchKind::LeftmostFirst)
+                    .build(literal_infix_strings)?,
+            )
+        } else {
+            None
+        };
+
+        let token_match = token_match_pattern
+            .map(|s| compile_pattern(RuleKind::TokenMatch, 0, s, &mut classification).map(|(_, re)| re))
+            .transpose()?;
+        let url_match = url_match_pattern
+            .map(|s| compile_pattern(RuleKind::UrlMatch, 0, s, &mut classification).map(|(_, re)| re))
+            .transpose()?;
+
+        for (key, tokens) in &exceptions {
+            validate_special_case(key, tokens)?;
+        }
+
+        let affix_and_infix_patterns = prefix_patterns.iter().chain(suffix_patterns).chain(regex_infix_patterns);
+        let plain_chunks = PlainChunks::for_rules(affix_and_infix_patterns.map(String::as_str), literal_infix_strings);
+
+        Ok(TokenizerRules {
+            prefixes,
+            suffixes,
+            regex_infixes,
+            literal_regex_infixes,
+            num_regex_infixes: regex_infix_patterns.len(),
+            literal_infix_matcher,
+            token_match,
+            url_match,
+            exceptions,
+            classification,
+            plain_chunks,
+        })
+    }
+
+    /// How each pattern was routed between Aho-Corasick, `regex` and `fancy_regex`.
+    pub fn classification_report(&self) -> &ClassificationReport {
+        &self.classification
+    }
+
+    pub fn num_prefix_patterns(&self) -> usize {
+        self.prefixes.len()
+    }
+
+    pub fn num_suffix_patterns(&self) -> usize {
+        self.suffixes.len()
+    }
+
+    pub fn num_regex_infix_patterns(&self) -> usize {
+        self.num_regex_infixes
+    }
+
+    pub fn num_literal_infix_patterns(&self) -> usize {
+        self.literal_infix_matcher.as_ref().map_or(0, |m| m.patterns_len())
+    }
+
+    pub fn num_exceptions(&self) -> usize {
+        self.exceptions.len()
+    }
+
+    /// The `NORM` that a special case sets on each of `tokens` (the tokens of `text`), if any.
+    /// Special cases match whole chunks, so the chunks of `text` are looked up again; a chunk
+    /// whose pieces were merged back into fewer tokens (see `tokenize_chunk`) gets no norms.
+    pub(crate) fn special_case_norms(&self, text: &str, tokens: &[Token]) -> Vec<Option<String>> {
+        let mut norms = vec![None; tokens.len()];
+        let (chunks, _, _) = split_chunks(text, TextPos::default());
+        for chunk in chunks {
+            let Some(pieces) = self.exceptions.get(chunk.text) else { continue };
+            let first = tokens.partition_point(|t| t.start_byte < chunk.start.byte);
+            let count = tokens[first..].iter().take_while(|t| t.end_byte <= chunk.end.byte).count();
+            if count == pieces.len() {
+                for (norm, piece) in norms[first..first + count].iter_mut().zip(pieces) {
+                    *norm = piece.get(NORM).cloned();
+                }
+            }
+        }
+        norms
+    }
+
+    /// Whether chunks made only of letters can skip the rules: true when no prefix, suffix or
+    /// infix pattern can match inside a run of letters.
+    pub fn has_plain_chunk_fast_path(&self) -> bool {
+        self.plain_chunks.is_some()
+    }
+}
+
+/// Checks a special case the way spaCy's `Tokenizer.add_special_case` does: every token must
+/// have an `ORTH`, only `ORTH` and `NORM` may be set, and the `ORTH` values must concatenate
+/// exactly to `key`. The key must not contain whitespace either: special cases are looked up
+/// per whitespace-delimited chunk, so such a key could never match.
+pub fn validate_special_case(key: &str, tokens: &[ExceptionAttributeMap]) -> Result<(), TokenizerError> {
+    let invalid = |reason: String| TokenizerError::InvalidSpecialCase { key: key.to_string(), reason };
+    if key.is_empty() {
+        return Err(invalid("the key must not be empty".to_string()));
+    }
+    if key.contains(char::is_whitespace) {
+        return Err(invalid("the key must not contain whitespace".to_string()));
+    }
+    if tokens.is_empty() {
+        return Err(invalid("at least one token is required".to_string()));
+    }
+    let mut concatenated = String::with_capacity(key.len());
+    for (i, attrs) in tokens.iter().enumerate() {
+        if let Some(attr) = attrs.keys().find(|k| k.as_str() != ORTH && k.as_str() != NORM) {
+            return Err(invalid(format!("token {} sets '{}'; only {} and {} are allowed", i, attr, ORTH, NORM)));
+        }
+        match attrs.get(ORTH) {
+            Some(orth) if !orth.is_empty() => concatenated.push_str(orth),
+            _ => return Err(invalid(format!("token {} has no {}", i, ORTH))),
+        }
+    }
+    if concatenated != key {
+        return Err(invalid(format!("{} values concatenate to '{}', not to the key", ORTH, concatenated)));
+    }
+    Ok(())
+}
+
+/// Validates `pattern` with `fancy_regex`, compiles it for its route and adds the route it was
+/// compiled for to `report`.
+fn compile_pattern(kind: RuleKind, index: usize, pattern: &str, report: &mut ClassificationReport) -> Result<(Route, RuleRegex), TokenizerError> {
+    if let Err(e) = classify::fancy_regex(pattern) {
+        return Err(TokenizerError::InvalidPattern { kind, index, pattern: pattern.to_string(), source: e });
+    }
+    let mut route = classify::route(pattern);
+    let re = RuleRegex::new(kind, index, pattern, &mut route)?;
+    report.patterns.push(classify::classification(kind, index, pattern, &route));
+    Ok((route, re))
+}
+
+
+/// Collects the regex runtime failures of one chunk according to the active `RegexErrorPolicy`.
+pub(crate) struct RegexErrorCollector<'c> {
+    policy: RegexErrorPolicy,
+    /// Receives the warnings of `RegexErrorPolicy::WarnAndFallback`.
+    sink: Option<&'c dyn TraceSink>,
+    chunk: &'c str,
+    chunk_start_char: usize,
+    errors: Vec<RuleError>,
+}
+
+impl<'c> RegexErrorCollector<'c> {
+    pub(crate) fn new(policy: RegexErrorPolicy, sink: Option<&'c dyn TraceSink>, chunk: &'c str, chunk_start_char: usize) -> Self {
+        RegexErrorCollector { policy, sink, chunk, chunk_start_char, errors: Vec::new() }
+    }
+
+    /// Records a failed rule. Returns `Err` only under `RegexErrorPolicy::Fail`;
+    /// otherwise the caller skips the rule for this chunk and carries on.
+    fn record(&mut self, kind: RuleKind, pattern_index: usize, pattern: &str, err: fancy_regex::Error) -> Result<(), TokenizerError> {
+        let rule_error = RuleError {
+            kind,
+            pattern_index,
+            pattern: pattern.to_string(),
+            chunk: self.chunk.to_string(),
+            chunk_start_char: self.chunk_start_char,
+            message: err.to_string(),
+        };
+        match self.policy {
+            RegexErrorPolicy::Fail => return Err(TokenizerError::RegexRuntime(rule_error)),
+            RegexErrorPolicy::WarnAndFallback => {
+                if let Some(sink) = self.sink {
+                    sink.rule_error(&rule_error);
+                }
+            }
+            RegexErrorPolicy::Count => {}
+        }
+        self.errors.push(rule_error);
+        Ok(())
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub(crate) fn into_errors(self) -> Vec<RuleError> {
+        self.errors
+    }
+}
+
+
+/// Tokenizes a single chunk of text, applying prefix, suffix, and infix rules.
+/// Returns the byte span of each token within the chunk, in order. The spans cover the
+/// chunk exactly, so every token is a slice of the input (special-case ORTH values are
+/// validated to concatenate to the chunk).
+///
+/// Regex runtime failures are handed to `regex_errors`, which decides whether to abort or skip the rule.
+/// When `rule_trace` is given, the rule that produced each token is pushed to it, in token order.
+/// Every rule match is reported to `tracer`. With `fast_path`, chunks made only of letters skip the
+/// rules when they cannot split them; `explain` and traced chunks always run the rules.
+///
+/// No token boundary falls inside a grapheme cluster: tokens the rules split a cluster across
+/// are merged back, keeping the rule of the first one.
+pub(crate) fn tokenize_chunk(
+    original_chunk: &str,
+    rules: &TokenizerRules,
+    regex_errors: &mut RegexErrorCollector,
+    mut rule_trace: Option<&mut Vec<TokenRule>>,
+    tracer: ChunkTracer,
+    fast_path: bool,
+) -> Result<Vec<(usize, usize)>, TokenizerError> {
+    let trace_start = rule_trace.as_ref().map_or(0, |trace| trace.len());
+    let mut token_spans = split_chunk(original_chunk, rules, regex_errors, rule_trace.as_deref_mut(), tracer, fast_path)?;
+    if let Some(starts_span) = offsets::merge_split_clusters(original_chunk, &mut token_spans) {
+        if let Some(trace) = rule_trace {
+            let mut starts_span = starts_span.into_iter();
+            let mut index = 0;
+            trace.retain(|_| {
+                index += 1;
+                index <= trace_start || starts_span.next().unwrap_or(true)
+            });
+        }
+    }
+    Ok(token_spans)
+}
+
+/// The rule pipeline of `tokenize_chunk`, before grapheme clusters are merged.
+fn split_chunk(
+    original_chunk: &str,
+    rules: &TokenizerRules,
+    regex_errors: &mut RegexErrorCollector,
+    mut rule_trace: Option<&mut Vec<TokenRule>>,
+    tracer: ChunkTracer,
+    fast_path: bool,
+) -> Result<Vec<(usize, usize)>, TokenizerError> {
+    tracer.emit_chunk(TraceStage::Chunk);
+    let mut token_spans: Vec<(usize, usize)> = Vec::new();
+
+    if original_chunk.is_empty() {
+        return Ok(token_spans);
+    }
+
+    // 1. Check for exact match in exceptions.
+    // Special cases are validated when added, so their ORTH values always cover the chunk exactly.
+    if let Some(exception_rules) = rules.exceptions.get(original_chunk) {
+        tracer.emit_chunk(TraceStage::Special);
+        let mut current_sub_offset = 0;
+        for (i, token_attrs_map) in exception_rules.iter().enumerate() {
+            if let Some(trace) = rule_trace.as_deref_mut() { trace.push(TokenRule::Special(i + 1)); }
+            let token_len = token_attrs_map[ORTH].len();
+            token_spans.push((current_sub_offset, current_sub_offset + token_len));
+            current_sub_offset += token_len;
+        }
+        return Ok(token_spans);
+    }
+
+    // 2. Plain runs of letters cannot be split by any rule (see `fastpath`); token_match
+    // and url_match could only keep them whole too.
+    if fast_path && rule_trace.is_none() && !tracer.is_enabled()
+        && rules.plain_chunks.as_ref().is_some_and(|plain| plain.is_plain(original_chunk))
+    {
+        return Ok(vec![(0, original_chunk.len())]);
+    }
+
+    // 3. Check for token_match (e.g., numbers, single-token emoticons, specific symbols)
+    // This catches entire chunks that should be single tokens
+    if let Some(re) = &rules.token_match {
+        match re.find_at(original_chunk, 0) {
+            Ok(Some((0, end))) if end == original_chunk.len() => {
+                tracer.emit_chunk(TraceStage::TokenMatch);
+                if let Some(trace) = rule_trace { trace.push(TokenRule::TokenMatch); }
+                return Ok(vec![(0, original_chunk.len())]);
+            }
+            Ok(_) => {}
+            Err(e) => regex_errors.record(RuleKind::TokenMatch, 0, re.as_str(), *e)?,
+        }
+    }
+
+    // 4. Check for url_match if the chunk is a URL
+    if let Some(re) = &rules.url_match {
+        match re.find_at(original_chunk, 0) {
+            Ok(Some((0, end))) if end == original_chunk.len() => {
+                tracer.emit_chunk(TraceStage::UrlMatch);
+                if let Some(trace) = rule_trace { trace.push(TokenRule::UrlMatch); }
+                return Ok(vec![(0, original_chunk.len())]);
+            }
+            Ok(_) => {}
+            Err(e) => regex_errors.record(RuleKind::UrlMatch, 0, re.as_str(), *e)?,
+        }
+    }
+
+    // If not handled by exceptions, token_match, or URL_match, proceed with splitting.
+    // The work slice is always `original_chunk[work_start..work_end]`.
+    let mut work_start = 0;
+    let mut work_end = original_chunk.len();
+
+    // --- Prefix Stripping ---
+    // Pattern indices are only looked up when someone is listening (explain/trace).
+    let wants_pattern_index = rule_trace.is_some() || tracer.is_enabled();
+    while work_start < work_end {
+        let current_work_slice = &original_chunk[work_start..work_end];
+        let on_error = |idx, pattern: &str, e| regex_errors.record(RuleKind::Prefix, idx, pattern, e);
+        let Some(mat) = rules.prefixes.find_prefix(current_work_slice, on_error)? else { break }; // No more prefixes matched
+        if wants_pattern_index {
+            let prefix_idx = rules.prefixes.pattern_index(current_work_slice, &mat);
+            tracer.emit(TraceStage::Prefix, Some(prefix_idx), current_work_slice, mat.start..mat.end);
+            if let Some(trace) = rule_trace.as_deref_mut() { trace.push(TokenRule::Prefix(prefix_idx)); }
+        }
+        token_spans.push((work_start, work_start + mat.end));
+        work_start += mat.end; // Continue with the text after the prefix
+    }
+
+    // --- Suffix Stripping ---
+    let mut suffixes_found_reversed: Vec<((usize, usize), Option<usize>)> = Vec::new(); // Store suffix spans (and their pattern index) to add them at the end
+    while work_start < work_end {
+        let current_work_slice = &original_chunk[work_start..work_end];
+        let on_error = |idx, pattern: &str, e| regex_errors.record(RuleKind::Suffix, idx, pattern, e);
+        let Some(mat) = rules.suffixes.find_suffix(current_work_slice, on_error)? else { break }; // No more suffixes matched
+        let suffix_idx = wants_pattern_index.then(|| rules.suffixes.pattern_index(current_work_slice, &mat));
+        tracer.emit(TraceStage::Suffix, suffix_idx, current_work_slice, mat.start..mat.end);
+        suffixes_found_reversed.push(((work_start + mat.start, work_end), suffix_idx));
+        work_end = work_start + mat.start; // Continue with the text before the suffix
+    }
+
+    // --- Infix Tokenization ---
+    if work_start < work_end {
+        let infix_parts = simple_infix_tokenize_chunk_internal(
+            &original_chunk[work_start..work_end],
+            rules.literal_infix_matcher.as_ref(),
+            rules.literal_regex_infixes.as_ref(),
+            &rules.regex_infixes,
+            regex_errors,
+            tracer,
+        )?;
+        // No prefix, suffix or infix matched: the chunk falls through as a single token.
+        if work_start == 0 && work_end == original_chunk.len() && infix_parts.len() == 1 {
+            tracer.emit_chunk(TraceStage::Fallback);
+        }
+        for (part_start, part_end, part_rule) in infix_parts {
+            if let Some(trace) = rule_trace.as_deref_mut() { trace.push(part_rule); }
+            token_spans.push((work_start + part_start, work_start + part_end));
+        }
+    }
+
+    // --- Re-attach Suffixes ---
+    for (suffix_span, suffix_idx) in suffixes_found_reversed.into_iter().rev() { // Re-reverse to original order
+        if let (Some(trace), Some(suffix_idx)) = (rule_trace.as_deref_mut(), suffix_idx) { trace.push(TokenRule::Suffix(suffix_idx)); }
+        token_spans.push(suffix_span);
+    }
+
+    // --- Fallback: If no splitting happened, take the whole chunk ---
+    if token_spans.is_empty() {
+        tracer.emit_chunk(TraceStage::Fallback);
+        if let Some(trace) = rule_trace { trace.push(TokenRule::Token); }
+        token_spans.push((0, original_chunk.len()));
+    }
+
+    Ok(token_spans)
+}
+
+/// Internal helper for infix tokenization within a given string slice.
+/// Returns the byte span (within `chunk`) of each infix-tokenized part, with the rule that produced it.
+fn simple_infix_tokenize_chunk_internal(
+    chunk: &str,
+    literal_matcher: Option<&AhoCorasick>,
+    literal_regex_infixes: Option<&LiteralSet>,
+    regex_infixes: &[(usize, RuleRegex)],
+    regex_errors: &mut RegexErrorCollector,
+    tracer: ChunkTracer,
+) -> Result<Vec<(usize, usize, TokenRule)>, TokenizerError> {
+    if chunk.is_empty() { return Ok(Vec::new()); }
+
+    let mut all_found_infix_spans: Vec<(usize, usize, TokenRule)> = Vec::new(); // (byte_start, byte_end, rule)
+
+    // 1. Find matches with AhoCorasick (literal infixes)
+    if let Some(matcher) = literal_matcher {
+        for mat in matcher.find_iter(chunk) {
+            if (mat.end() - mat.start()) > 0 {
+                tracer.emit(TraceStage::LiteralInfix, Some(mat.pattern().as_usize()), chunk, mat.range());
+                all_found_infix_spans.push((mat.start(), mat.end(), TokenRule::LiteralInfix(mat.pattern().as_usize())));
+            }
+        }
+    }
+
+    // 2. Find matches of the regex infixes: the literal ones with Aho-Corasick, the rest
+    // with `regex`/`fancy_regex` (see `classify`)
+    if let Some(literals) = literal_regex_infixes {
+        for (start, end, pattern_idx) in literals.find_all(chunk) {
+            tracer.emit(TraceStage::Infix, Some(pattern_idx), chunk, start..end);
+            all_found_infix_spans.push((start, end, TokenRule::Infix(pattern_idx)));
+        }
+    }
+    for (pattern_idx, re) in regex_infixes {
+        match re.find_all(chunk) {
+            Ok(iter_matches) => {
+                for (start, end) in iter_matches {
+                    if end > start {
+                        tracer.emit(TraceStage::Infix, Some(*pattern_idx), chunk, start..end);
+                        all_found_infix_spans.push((start, end, TokenRule::Infix(*pattern_idx)));
+                    }
+                }
+            }
+            Err(e) => regex_errors.record(RuleKind::Infix, *pattern_idx, re.as_str(), *e)?,
+        }
+    }
+
+    if all_found_infix_spans.is_empty() {
+        return Ok(vec![(0, chunk.len(), TokenRule::Token)]);
+    }
+
+    // 3. Combine, Sort, and Filter Overlapping Matches
+    // Sort by start position, then by length (longer first) to correctly handle overlaps
+    // This mimics spaCy's preference for longest match at the same starting point.
+    // Exact ties go to literal infixes first, then to regex infixes in rule order.
+    let rule_rank = |rule: &TokenRule| match rule {
+        TokenRule::Infix(i) => i + 1,
+        _ => 0,
+    };
+    all_found_infix_spans.sort_by_key(|k| (k.0, std::cmp::Reverse(k.1 - k.0), rule_rank(&k.2)));
+
+    let mut filtered_matches: Vec<(usize, usize, TokenRule)> = Vec::new();
+    let mut current_processed_byte_end = 0;
+    for &(byte_start, byte_end, rule) in &all_found_infix_spans {
+        if byte_start >= current_processed_byte_end {
+            // New match, no overlap or past previous match
+            filtered_matches.push((byte_start, byte_end, rule));
+            current_processed_byte_end = byte_end;
+        } else if byte_end > current_processed_byte_end {
+            // Overlap: If new match extends further, update end of last match.
+            // This is a simplified merge. spaCy has more complex rules for merging.
+            let last_match_idx = filtered_matches.len() - 1;
+            filtered_matches[last_match_idx].1 = byte_end;
+            current_processed_byte_end = byte_end;
+        }
+    }
+    // Re-sort by start position (should mostly be sorted already due to previous sort and merge logic)
+    filtered_matches.sort_by_key(|k| k.0);
+
+    // 4. Split chunk into tokens based on filtered_matches
+    let mut tokens: Vec<(usize, usize, TokenRule)> = Vec::new();
+    let mut last_byte_end = 0;
+
+    for (byte_start, byte_end, rule) in filtered_matches {
+        // Add text before the infix match
+        if byte_start > last_byte_end {
+            tokens.push((last_byte_end, byte_start, TokenRule::Token));
+        }
+        // Add the infix match itself
+        tokens.push((byte_start, byte_end, rule));
+        last_byte_end = byte_end;
+    }
+
+    // Add any remaining text after the last infix match
+    if last_byte_end < chunk.len() {
+        tokens.push((last_byte_end, chunk.len(), TokenRule::Token));
+    }
+    Ok(tokens)
+}
+
+
+/// A whitespace-delimited chunk of the input, with its absolute start and end positions.
+struct ChunkInfo<'a> {
+    text: &'a str,
+    start: TextPos,
+    end: TextPos,
+    /// The chunk is directly followed by a single space (spaCy's SPACY flag).
+    space_after: bool,
+}
+
+/// Splits `text`, whose first char sits at `base` of a larger input, into whitespace-delimited
+/// chunks in a single walk. Returns the chunks, the ranges of `chunks` on each line that has any
+/// (lines end at `\n`), and the position of the end of `text`.
+///
+/// The walk goes by grapheme cluster, and a cluster that starts with whitespace counts as
+/// whitespace, so a combining mark after a space stays with the space rather than starting a chunk.
+fn split_chunks(text: &str, base: TextPos) -> (Vec<ChunkInfo<'_>>, Vec<Range<usize>>, TextPos) {
+    let mut chunks: Vec<ChunkInfo> = Vec::new();
+    let mut lines: Vec<Range<usize>> = Vec::new();
+    let mut line_start = 0;
+    let mut chunk_start: Option<TextPos> = None;
+    let mut pos = base;
+    for cluster in offsets::clusters(text) {
+        if cluster.starts_with(char::is_whitespace) {
+            if let Some(start) = chunk_start.take() {
+                chunks.push(ChunkInfo {
+                    text: &text[start.byte - base.byte..pos.byte - base.byte],
+                    start,
+                    end: pos,
+                    space_after: cluster == " ",
+                });
+            }
+            if cluster.ends_with('\n') && line_start < chunks.len() {
+                lines.push(line_start..chunks.len());
+                line_start = chunks.len();
+            }
+        } else if chunk_start.is_none() {
+            chunk_start = Some(pos);
+        }
+        pos.advance_cluster(cluster);
+    }
+    if let Some(start) = chunk_start {
+        chunks.push(ChunkInfo { text: &text[start.byte - base.byte..], start, end: pos, space_after: false });
+    }
+    if line_start < chunks.len() {
+        lines.push(line_start..chunks.len());
+    }
+    (chunks, lines, pos)
+}
+
+/// Tokenizes `chunks`, in parallel if the `settings` say so, and returns their tokens in order
+/// with absolute offsets, plus the rule errors recorded under the `settings`' error policy.
+fn tokenize_chunks<'t>(
+    chunks: &[ChunkInfo<'t>],
+    rules: &TokenizerRules,
+    settings: ChunkSettings,
+) -> Result<(Vec<TokenRef<'t>>, Vec<RuleError>), TokenizerError> {
+    let ChunkSettings { regex_error_policy, trace_sink, cache, fast_path, parallel_chunks } = settings;
+    let tokenize_one = |chunk: &ChunkInfo<'t>| -> Result<(Vec<TokenRef<'t>>, Vec<RuleError>), TokenizerError> {
+        let mut regex_errors = RegexErrorCollector::new(regex_error_policy, trace_sink, chunk.text, chunk.start.char);
+        let tracer = ChunkTracer::new(trace_sink, chunk.text, chunk.start.char);
+        let token_spans: Arc<[(usize, usize)]> = match cache.and_then(|cache| cache.get(chunk.text)) {
+            Some(cached) => cached,
+            None => {
+                let token_spans = tokenize_chunk(chunk.text, rules, &mut regex_errors, None, tracer, fast_path)?;
+                // A split that skipped a failing rule is not cached, so the failure is reported every time.
+                if let Some(cache) = cache.filter(|_| regex_errors.is_empty()) {
+                    cache.insert(chunk.text, &token_spans);
+                }
+                token_spans.into()
+            }
+        };
+
+        // The spans cover the chunk in order, so each token starts where the previous one ended.
+        // In an ASCII chunk (as many bytes as chars) every offset advances by the byte length;
+        // otherwise by the token's grapheme clusters, which it never splits.
+        let ascii = chunk.end.byte - chunk.start.byte == chunk.end.char - chunk.start.char;
+        let mut pos = chunk.start;
+        let num_tokens = token_spans.len();
+        let tokens: Vec<TokenRef<'t>> = token_spans.iter().enumerate()
+            .map(|(i, &(start, end))| {
+                let text = &chunk.text[start..end];
+                let token_start = pos;
+                if ascii { pos.advance_ascii(text) } else { pos.advance_str(text) }
+                TokenRef::new(text, token_start, pos, chunk.space_after && i + 1 == num_tokens)
+            })
+            .collect();
+        Ok((tokens, regex_errors.into_errors()))
+    };
+    // `collect` keeps the input order, in parallel too.
+    let tokenized_chunks: Vec<(Vec<TokenRef<'t>>, Vec<RuleError>)> = if parallel_chunks {
+        chunks.par_iter().map(tokenize_one).collect::<Result<_, TokenizerError>>()?
+    } else {
+        chunks.iter().map(tokenize_one).collect::<Result<_, TokenizerError>>()?
+    };
+
+    let mut tokens: Vec<TokenRef<'t>> = Vec::new();
+    let mut rule_errors: Vec<RuleError> = Vec::new();
+    for (chunk_tokens, chunk_rule_errors) in tokenized_chunks {
+        tokens.extend(chunk_tokens);
+        rule_errors.extend(chunk_rule_errors);
+    }
+    Ok((tokens, rule_errors))
+}
+
+/// Per-call settings of `tokenize_chunks`.
+#[derive(Copy, Clone)]
+pub(crate) struct ChunkSettings<'a> {
+    pub(crate) regex_error_policy: RegexErrorPolicy,
+    /// Receives the trace events, if any.
+    pub(crate) trace_sink: Option<&'a dyn TraceSink>,
+    /// Chunk splits are looked up in and added to this cache, if any.
+    pub(crate) cache: Option<&'a ChunkCache>,
+    /// Let plain chunks skip the rules (see `tokenize_chunk`).
+    pub(crate) fast_path: bool,
+    /// Tokenize the chunks in parallel. Callers that already run in parallel (over lines or
+    /// documents) leave it off to avoid rayon overhead on small chunks.
+    pub(crate) parallel_chunks: bool,
+}
+
+
+/// Fills the whitespace gaps between `tokens` the way spaCy does, so no input byte is lost.
+///
+/// A gap that starts with a single space after a token sets that token's `whitespace_after`
+/// (already done per chunk) and only the rest of the gap becomes a whitespace token; any other
+/// gap (leading whitespace, tabs, newlines, runs of spaces) is emitted as one whitespace token.
+///
+/// `text` runs from `base` to `end`; a gap runs from the end of a token (or `base`) to the start
+/// of the next one (or `end`), so its positions are known without looking at it.
+fn insert_whitespace_tokens<'t>(text: &'t str, base: TextPos, end: TextPos, tokens: Vec<TokenRef<'t>>) -> Vec<TokenRef<'t>> {
+    let mut with_ws: Vec<TokenRef<'t>> = Vec::with_capacity(tokens.len());
+
+    let push_gap = |with_ws: &mut Vec<TokenRef<'t>>, mut gap_start: TextPos, gap_end: TextPos| {
+        if gap_start.byte < gap_end.byte && with_ws.last().is_some_and(|t| t.whitespace_after) {
+            // The first space is owned by the previous token's trailing whitespace.
+            gap_start.advance_ascii(" ");
+        }
+        if gap_start.byte < gap_end.byte {
+            let gap = &text[gap_start.byte - base.byte..gap_end.byte - base.byte];
+            with_ws.push(TokenRef::new(gap, gap_start, gap_end, false));
+        }
+    };
+
+    let mut pos = base;
+    for token in tokens {
+        push_gap(&mut with_ws, pos, token.start_pos());
+        pos = token.end_pos();
+        with_ws.push(token);
+    }
+    push_gap(&mut with_ws, pos, end);
+    with_ws
+}
+
+
+/// Default number of documents per rayon task in `Tokenizer::tokenize_batch`.
+pub const DEFAULT_BATCH_SIZE: usize = 64;
+
+/// Tokens of one document and the rules skipped on it, as `Tokenizer::tokenize_with_report`.
+type Tokenized<T> = (Vec<T>, Vec<RuleError>);
+
+/// A reusable tokenizer over a shared, immutable set of `TokenizerRules`.
+///
+/// Cloning a `Tokenizer` is cheap: the compiled rules are reference-counted.
+#[derive(Clone)]
+pub struct Tokenizer {
+    rules: Arc<TokenizerRules>,
+    regex_error_policy: RegexErrorPolicy,
+    trace_sink: Option<Arc<dyn TraceSink>>,
+    batch_size: usize,
+    thread_pool: Option<Arc<ThreadPool>>,
+    chunk_cache: Option<Arc<ChunkCache>>,
+    fast_path: bool,
+}
+
+impl Tokenizer {
+    pub fn new(rules: TokenizerRules) -> Self {
+        Tokenizer { rules: Arc::new(rules), regex_error_policy: RegexErrorPolicy::default(), trace_sink: None, batch_size: DEFAULT_BATCH_SIZE, thread_pool: None,
+            chunk_cache: Some(Arc::new(ChunkCache::new(DEFAULT_CACHE_CAPACITY))), fast_path: true }
+    }
+
+    /// Sends trace events of every call to `sink` (e.g. `StderrTraceSink`).
+    /// Use `tokenize_traced` to trace a single call instead.
+    pub fn with_trace_sink(mut self, sink: Arc<dyn TraceSink>) -> Self {
+        self.trace_sink = Some(sink);
+        self
+    }
+
+    /// Sets how rules that fail at match time are handled (default: `RegexErrorPolicy::Count`).
+    pub fn with_regex_error_policy(mut self, policy: RegexErrorPolicy) -> Self {
+        self.regex_error_policy = policy;
+        self
+    }
+
+    pub fn regex_error_policy(&self) -> RegexErrorPolicy {
+        self.regex_error_policy
+    }
+
+    /// Sets how many documents `tokenize_batch` hands to each rayon task (default `DEFAULT_BATCH_SIZE`).
+    /// Larger batches cut scheduling overhead for many short documents; smaller ones balance long ones.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Runs all parallel work on `pool` instead of the rayon pool of the calling thread
+    /// (the global pool outside of any `install`). The tokenizer never configures the global pool.
+    pub fn with_thread_pool(mut self, pool: Arc<ThreadPool>) -> Self {
+        self.thread_pool = Some(pool);
+        self
+    }
+
+    /// Like `with_thread_pool`, with a new pool of `num_threads` threads owned by this tokenizer
+    /// (and shared by its clones). `0` lets rayon pick the number of threads.
+    pub fn with_num_threads(self, num_threads: usize) -> Result<Self, TokenizerError> {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .thread_name(|i| format!("tokenizer-{}", i))
+            .build()?;
+        Ok(self.with_thread_pool(Arc::new(pool)))
+    }
+
+    pub fn thread_pool(&self) -> Option<&Arc<ThreadPool>> {
+        self.thread_pool.as_ref()
+    }
+
+    /// Replaces the chunk cache with an empty one holding up to `capacity` chunks
+    /// (default `DEFAULT_CACHE_CAPACITY`); `0` disables caching.
+    ///
+    /// The cache maps each whitespace-delimited chunk to its split, so repeated chunks skip the
+    /// rules. It is shared by clones of this tokenizer until their special cases diverge.
+    pub fn with_chunk_cache(mut self, capacity: usize) -> Self {
+        self.chunk_cache = (capacity > 0).then(|| Arc::new(ChunkCache::new(capacity)));
+        self
+    }
+
+    /// Disables the chunk cache, as `with_chunk_cache(0)`.
+    pub fn without_chunk_cache(self) -> Self {
+        self.with_chunk_cache(0)
+    }
+
+    /// Hit/miss counters and size of the chunk cache, or `None` if it is disabled.
+    pub fn chunk_cache_stats(&self) -> Option<CacheStats> {
+        self.chunk_cache.as_ref().map(|cache| cache.stats())
+    }
+
+    /// Empties the chunk cache (the counters are kept).
+    pub fn clear_chunk_cache(&self) {
+        if let Some(cache) = &self.chunk_cache {
+            cache.clear();
+        }
+    }
+
+    /// Gives this tokenizer a fresh chunk cache after its rules changed; clones keep theirs.
+    fn reset_chunk_cache(&mut self) {
+        if let Some(cache) = &self.chunk_cache {
+            self.chunk_cache = Some(Arc::new(ChunkCache::new(cache.capacity())));
+        }
+    }
+
+    /// Enables or disables the shortcut for chunks made only of letters (on by default; it only
+    /// applies when `TokenizerRules::has_plain_chunk_fast_path`). The output is the same either way.
+    pub fn with_fast_path(mut self, enabled: bool) -> Self {
+        self.fast_path = enabled;
+        self
+    }
+
+    /// Runs `op` on the tokenizer's thread pool, if it has one.
+    fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+        match &self.thread_pool {
+            Some(pool) => pool.install(op),
+            None => op(),
+        }
+    }
+
+    /// Adds (or replaces) a special-case tokenization rule, as spaCy's `add_special_case`.
+    ///
+    /// Rejects the rule with `TokenizerError::InvalidSpecialCase` if it fails `validate_special_case`.
+    /// Other clones of this tokenizer keep their previous rules.
+    pub fn add_special_case(&mut self, key: &str, tokens: Vec<ExceptionAttributeMap>) -> Result<(), TokenizerError> {
+        validate_special_case(key, &tokens)?;
+        Arc::make_mut(&mut self.rules).exceptions.insert(key.to_string(), tokens);
+        self.reset_chunk_cache();
+        Ok(())
+    }
+
+    /// Removes a special-case rule, returning its tokens if it existed.
+    pub fn remove_special_case(&mut self, key: &str) -> Option<Vec<ExceptionAttributeMap>> {
+        if !self.rules.exceptions.contains_key(key) {
+            return None;
+        }
+        self.reset_chunk_cache();
+        Arc::make_mut(&mut self.rules).exceptions.remove(key)
+    }
+
+    /// Builds a tokenizer over the default English rules.
+    pub fn english() -> Result<Self, TokenizerError> {
+        Ok(Tokenizer::new(TokenizerRules::new()?))
+    }
+
+    pub fn rules(&self) -> &TokenizerRules {
+        &self.rules
+    }
+
+    /// Tokenizes `text` line by line and returns the tokens in order,
+    /// with char and byte offsets relative to the start of `text`.
+    ///
+    /// Like spaCy, whitespace other than a single space after a token (extra spaces,
+    /// tabs, newlines) is kept as whitespace tokens, so the output is lossless.
+    pub fn tokenize(&self, text: &str) -> Result<Vec<Token>, TokenizerError> {
+        Ok(self.tokenize_with_report(text)?.0)
+    }
+
+    /// Like `tokenize`, but also returns every rule that failed at match time and was
+    /// skipped under the tokenizer's `RegexErrorPolicy`.
+    pub fn tokenize_with_report(&self, text: &str) -> Result<(Vec<Token>, Vec<RuleError>), TokenizerError> {
+        let (tokens, rule_errors) = self.tokenize_lines(text, TextPos::default(), self.trace_sink.as_deref(), true)?;
+        Ok((tokens.into_iter().map(Token::from).collect(), rule_errors))
+    }
+
+    /// Like `tokenize_with_report`, but sends this call's trace events to `sink`
+    /// (in place of the tokenizer's own sink, if any).
+    pub fn tokenize_traced(&self, text: &str, sink: &dyn TraceSink) -> Result<(Vec<Token>, Vec<RuleError>), TokenizerError> {
+        let (tokens, rule_errors) = self.tokenize_lines(text, TextPos::default(), Some(sink), true)?;
+        Ok((tokens.into_iter().map(Token::from).collect(), rule_errors))
+    }
+
+    /// Tokenizes the text read from `reader` batch by batch, yielding owned tokens in order
+    /// with memory bounded by the batch size (see `TokenStream`).
+    pub fn tokenize_reader<R: BufRead>(&self, reader: R) -> TokenStream<R> {
+        TokenStream::new(self.clone(), reader)
+    }
+
+    pub(crate) fn trace_sink(&self) -> Option<&dyn TraceSink> {
+        self.trace_sink.as_deref()
+    }
+
+    /// Like `tokenize`, but without copying: each `TokenRef` borrows its text from `text`.
+    pub fn tokenize_borrowed<'t>(&self, text: &'t str) -> Result<Vec<TokenRef<'t>>, TokenizerError> {
+        Ok(self.tokenize_borrowed_with_report(text)?.0)
+    }
+
+    /// Like `tokenize_with_report`, returning borrowed tokens.
+    pub fn tokenize_borrowed_with_report<'t>(&self, text: &'t str) -> Result<(Vec<TokenRef<'t>>, Vec<RuleError>), TokenizerError> {
+        self.tokenize_lines(text, TextPos::default(), self.trace_sink.as_deref(), true)
+    }
+
+    /// Tokenizes `text`, whose first char sits at `base` of a larger input (the start for a whole
+    /// text; the batch position for `TokenStream`). Offsets are relative to that input.
+    ///
+    /// The text is walked once to find its chunks and their offsets. With `parallel` set, lines
+    /// are tokenized in parallel, or the chunks of the line if there is only one; without it
+    /// (e.g. under `tokenize_batch`, which runs documents in parallel) everything runs on the
+    /// calling thread.
+    pub(crate) fn tokenize_lines<'t>(
+        &self,
+        text: &'t str,
+        base: TextPos,
+        trace_sink: Option<&dyn TraceSink>,
+        parallel: bool,
+    ) -> Result<(Vec<TokenRef<'t>>, Vec<RuleError>), TokenizerError> {
+        if parallel && self.thread_pool.is_some() {
+            return self.install(|| self.tokenize_lines_on_current_pool(text, base, trace_sink, parallel));
+        }
+        self.tokenize_lines_on_current_pool(text, base, trace_sink, parallel)
+    }
+
+    fn tokenize_lines_on_current_pool<'t>(
+        &self,
+        text: &'t str,
+        base: TextPos,
+        trace_sink: Option<&dyn TraceSink>,
+        parallel: bool,
+    ) -> Result<(Vec<TokenRef<'t>>, Vec<RuleError>), TokenizerError> {
+        let (chunks, lines, end) = split_chunks(text, base);
+        let parallel_lines = parallel && lines.len() > 1;
+        // Traced calls skip the cache so every chunk reports its rule matches.
+        let cache = self.chunk_cache.as_deref().filter(|_| trace_sink.is_none());
+        let settings = ChunkSettings {
+            regex_error_policy: self.regex_error_policy,
+            trace_sink,
+            cache,
+            fast_path: self.fast_path,
+            parallel_chunks: parallel && !parallel_lines,
+        };
+        let tokenize_line = |line: Range<usize>| tokenize_chunks(&chunks[line], &self.rules, settings);
+        let tokenized_lines: Vec<(Vec<TokenRef<'t>>, Vec<RuleError>)> = if parallel_lines {
+            lines.into_par_iter().map(tokenize_line).collect::<Result<_, TokenizerError>>()?
+        } else {
+            lines.into_iter().map(tokenize_line).collect::<Result<_, TokenizerError>>()?
+        };
+
+        let mut all_tokens: Vec<TokenRef<'t>> = Vec::new();
+        let mut all_rule_errors: Vec<RuleError> = Vec::new();
+        for (tokens_for_line, rule_errors_for_line) in tokenized_lines {
+            all_tokens.extend(tokens_for_line);
+            all_rule_errors.extend(rule_errors_for_line);
+        }
+        Ok((insert_whitespace_tokens(text, base, end, all_tokens), all_rule_errors))
+    }
+
+    /// Tokenizes many documents at once, as spaCy's `nlp.pipe`: documents are tokenized in
+    /// parallel, `batch_size` per rayon task (see `with_batch_size`), and each one on a single
+    /// thread. Returns the tokens of each document, in input order.
+    /// Rules skipped under the `RegexErrorPolicy` are dropped, as in `tokenize`.
+    pub fn tokenize_batch(&self, texts: &[&str]) -> Result<Vec<Vec<Token>>, TokenizerError> {
+        Ok(self.tokenize_batch_with_report(texts)?.into_iter().map(|(tokens, _)| tokens).collect())
+    }
+
+    /// Like `tokenize_batch`, but also returns, for each document, the rules that failed at
+    /// match time on it (as `tokenize_with_report`).
+    pub fn tokenize_batch_with_report(&self, texts: &[&str]) -> Result<Vec<Tokenized<Token>>, TokenizerError> {
+        Ok(self.tokenize_batch_borrowed_with_report(texts)?
+            .into_iter()
+            .map(|(tokens, rule_errors)| (tokens.into_iter().map(Token::from).collect(), rule_errors))
+            .collect())
+    }
+
+    /// Like `tokenize_batch`, returning tokens that borrow from `texts`.
+    pub fn tokenize_batch_borrowed<'t>(&self, texts: &[&'t str]) -> Result<Vec<Vec<TokenRef<'t>>>, TokenizerError> {
+        Ok(self.tokenize_batch_borrowed_with_report(texts)?.into_iter().map(|(tokens, _)| tokens).collect())
+    }
+
+    /// Like `tokenize_batch_with_report`, returning tokens that borrow from `texts`.
+    pub fn tokenize_batch_borrowed_with_report<'t>(&self, texts: &[&'t str]) -> Result<Vec<Tokenized<TokenRef<'t>>>, TokenizerError> {
+        let trace_sink = self.trace_sink.as_deref();
+        let batches: Vec<Vec<Tokenized<TokenRef<'t>>>> = self.install(|| {
+            texts.par_chunks(self.batch_size)
+                .map(|batch| {
+                    batch.iter()
+                        .map(|text| self.tokenize_lines(text, TextPos::default(), trace_sink, false))
+                        .collect::<Result<Vec<_>, TokenizerError>>()
+                })
+                .collect::<Result<_, TokenizerError>>()
+        })?;
+        Ok(batches.into_iter().flatten().collect())
+    }
+
+    /// Like `make_doc` for many documents, tokenized as `tokenize_batch`. Each `Doc` carries the
+    /// rules skipped on it.
+    pub fn make_docs(&self, texts: &[&str]) -> Result<Vec<Doc>, TokenizerError> {
+        Ok(self.tokenize_batch_with_report(texts)?
+            .into_iter()
+            .zip(texts)
+            .map(|((tokens, rule_errors), text)| self.doc(text, tokens, rule_errors))
+            .collect())
+    }
+
+    /// Tokenizes `text` into a `Doc` that owns the text and round-trips to it exactly.
+    /// Rules skipped under the `RegexErrorPolicy` are available from `Doc::rule_errors`.
+    pub fn make_doc(&self, text: &str) -> Result<Doc, TokenizerError> {
+        let (tokens, rule_errors) = self.tokenize_with_report(text)?;
+        Ok(self.doc(text, tokens, rule_errors))
+    }
+
+    /// Builds the `Doc` of `text`, with the `NORM` of the tokens that come from a special case.
+    fn doc(&self, text: &str, tokens: Vec<Token>, rule_errors: Vec<RuleError>) -> Doc {
+        let norms = self.rules.special_case_norms(text, &tokens);
+        Doc::new(text.to_string(), tokens).with_norms(norms).with_rule_errors(rule_errors)
+    }
+
+    /// Like spaCy's `nlp.tokenizer.explain()`: returns each token paired with the rule that produced it.
+    /// Whitespace tokens are not rules' output and are left out. Chunks are processed sequentially.
+    /// Rules skipped under the `RegexErrorPolicy` are dropped, as in `tokenize`.
+    pub fn explain(&self, text: &str) -> Result<Vec<Explanation>, TokenizerError> {
+        Ok(self.explain_with_report(text)?.0)
+    }
+
+    /// Like `explain`, but also returns the rules that failed at match time and were skipped,
+    /// so a fallback under `RegexErrorPolicy::WarnAndFallback` shows up next to the explanation.
+    pub fn explain_with_report(&self, text: &str) -> Result<(Vec<Explanation>, Vec<RuleError>), TokenizerError> {
+        let mut explanations = Vec::new();
+        let mut rule_errors = Vec::new();
+        let (chunks, _, _) = split_chunks(text, TextPos::default());
+        for chunk in chunks {
+            let sink = self.trace_sink.as_deref();
+            let mut regex_errors = RegexErrorCollector::new(self.regex_error_policy, sink, chunk.text, chunk.start.char);
+            let mut rules_for_chunk = Vec::new();
+            let tracer = ChunkTracer::new(sink, chunk.text, chunk.start.char);
+            let spans = tokenize_chunk(chunk.text, &self.rules, &mut regex_errors, Some(&mut rules_for_chunk), tracer, false)?;
+            debug_assert_eq!(spans.len(), rules_for_chunk.len());
+            explanations.extend(spans.into_iter().zip(rules_for_chunk)
+                .map(|((start, end), rule)| Explanation { rule, text: chunk.text[start..end].to_string() }));
+            rule_errors.extend(regex_errors.into_errors());
+        }
+        Ok((explanations, rule_errors))
+    }
+}
+
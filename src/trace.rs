@@ -0,0 +1,172 @@
+// src/trace.rs
+use std::fmt;
+use std::ops::Range;
+use std::sync::Mutex;
+
+use crate::error::RuleError;
+
+/// The tokenizer stage a `TraceEvent` comes from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TraceStage {
+    /// A whitespace-delimited chunk is about to be tokenized.
+    Chunk,
+    /// The chunk matched a special case.
+    Special,
+    TokenMatch,
+    UrlMatch,
+    Prefix,
+    Suffix,
+    /// A regex infix match (before overlapping matches are merged).
+    Infix,
+    /// A literal (Aho-Corasick) infix match (before overlapping matches are merged).
+    LiteralInfix,
+    /// No rule split the chunk; it becomes a single token.
+    Fallback,
+}
+
+impl TraceStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TraceStage::Chunk => "chunk",
+            TraceStage::Special => "special",
+            TraceStage::TokenMatch => "token_match",
+            TraceStage::UrlMatch => "url_match",
+            TraceStage::Prefix => "prefix",
+            TraceStage::Suffix => "suffix",
+            TraceStage::Infix => "infix",
+            TraceStage::LiteralInfix => "literal_infix",
+            TraceStage::Fallback => "fallback",
+        }
+    }
+}
+
+impl fmt::Display for TraceStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One step of the tokenizer's work on a chunk.
+///
+/// Chunks are tokenized in parallel, so events of different chunks can interleave;
+/// `chunk_start_char` identifies the chunk within the text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceEvent {
+    pub chunk: String,
+    /// Char offset of the chunk within the tokenized text.
+    pub chunk_start_char: usize,
+    pub stage: TraceStage,
+    /// Index of the matching pattern within its rule list, for prefix/suffix/infix stages.
+    pub rule_index: Option<usize>,
+    /// Byte span of the match within `chunk`.
+    pub span: Range<usize>,
+}
+
+impl TraceEvent {
+    /// The matched text.
+    pub fn matched(&self) -> &str {
+        &self.chunk[self.span.clone()]
+    }
+}
+
+impl fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[trace] chunk {:?}@{} {}", self.chunk, self.chunk_start_char, self.stage)?;
+        if let Some(i) = self.rule_index {
+            write!(f, " #{}", i)?;
+        }
+        write!(f, " bytes {}..{} {:?}", self.span.start, self.span.end, self.matched())
+    }
+}
+
+/// Receives `TraceEvent`s. Called concurrently from the chunk worker threads.
+///
+/// Any `Fn(&TraceEvent) + Send + Sync` closure is a sink.
+pub trait TraceSink: Send + Sync {
+    fn event(&self, event: &TraceEvent);
+
+    /// A rule failed at match time under `RegexErrorPolicy::WarnAndFallback` and was skipped
+    /// for this chunk. Ignored by default.
+    fn rule_error(&self, _error: &RuleError) {}
+}
+
+impl<F: Fn(&TraceEvent) + Send + Sync> TraceSink for F {
+    fn event(&self, event: &TraceEvent) {
+        self(event)
+    }
+}
+
+/// Writes one line per event to stderr, so traces never mix with stdout output.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct StderrTraceSink;
+
+impl TraceSink for StderrTraceSink {
+    fn event(&self, event: &TraceEvent) {
+        eprintln!("{}", event);
+    }
+
+    fn rule_error(&self, error: &RuleError) {
+        eprintln!("[warning] {}", error);
+    }
+}
+
+/// Keeps every event in memory.
+#[derive(Debug, Default)]
+pub struct CollectTraceSink {
+    events: Mutex<Vec<TraceEvent>>,
+}
+
+impl CollectTraceSink {
+    pub fn new() -> Self {
+        CollectTraceSink::default()
+    }
+
+    /// The collected events, sorted by chunk position (events of one chunk keep their order).
+    pub fn into_events(self) -> Vec<TraceEvent> {
+        let mut events = self.events.into_inner().unwrap_or_else(|e| e.into_inner());
+        events.sort_by_key(|e| e.chunk_start_char);
+        events
+    }
+}
+
+impl TraceSink for CollectTraceSink {
+    fn event(&self, event: &TraceEvent) {
+        self.events.lock().unwrap_or_else(|e| e.into_inner()).push(event.clone());
+    }
+}
+
+/// Emits the events of one chunk to an optional sink. Does nothing when tracing is off.
+#[derive(Copy, Clone)]
+pub(crate) struct ChunkTracer<'a> {
+    sink: Option<&'a dyn TraceSink>,
+    chunk: &'a str,
+    chunk_start_char: usize,
+}
+
+impl<'a> ChunkTracer<'a> {
+    pub(crate) fn new(sink: Option<&'a dyn TraceSink>, chunk: &'a str, chunk_start_char: usize) -> Self {
+        ChunkTracer { sink, chunk, chunk_start_char }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.sink.is_some()
+    }
+
+    /// Emits an event for the byte range `span` of `within`, which must be a subslice of the chunk.
+    pub(crate) fn emit(&self, stage: TraceStage, rule_index: Option<usize>, within: &str, span: Range<usize>) {
+        let Some(sink) = self.sink else { return };
+        let base = within.as_ptr() as usize - self.chunk.as_ptr() as usize;
+        sink.event(&TraceEvent {
+            chunk: self.chunk.to_string(),
+            chunk_start_char: self.chunk_start_char,
+            stage,
+            rule_index,
+            span: base + span.start..base + span.end,
+        });
+    }
+
+    /// Emits an event covering the whole chunk.
+    pub(crate) fn emit_chunk(&self, stage: TraceStage) {
+        self.emit(stage, None, self.chunk, 0..self.chunk.len());
+    }
+}
@@ -0,0 +1,46 @@
+// tests/affixes.rs
+//! Which prefix or suffix wins when several patterns match, as spaCy's combined
+//! `prefix_search`/`suffix_search` regexes decide it.
+
+use std::collections::HashMap;
+
+use my_spacy_tokenizer::{Tokenizer, TokenizerRules};
+
+/// The tokens of `text` under the given prefixes and suffixes, with the rule behind each.
+fn explained(prefixes: &[&str], suffixes: &[&str], text: &str) -> Vec<String> {
+    let strings = |patterns: &[&str]| patterns.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+    let rules = TokenizerRules::from_patterns(&strings(prefixes), &strings(suffixes), &[], &[], None, None, HashMap::new()).unwrap();
+    Tokenizer::new(rules).explain(text).unwrap().iter().map(|e| format!("{}:{}", e.text, e.rule)).collect()
+}
+
+#[test]
+fn the_leftmost_suffix_wins_over_rule_order() {
+    // `b` is listed first but `ab` starts earlier, whether both are literals or not.
+    assert_eq!(explained(&[], &["b", "ab"], "xab"), ["x:TOKEN", "ab:SUFFIX#1"]);
+    assert_eq!(explained(&[], &["b", r"\wb"], "xab"), ["x:TOKEN", "ab:SUFFIX#1"]);
+    assert_eq!(explained(&[], &["b", r"a(?=b)b"], "xab"), ["x:TOKEN", "ab:SUFFIX#1"]);
+}
+
+#[test]
+fn suffixes_starting_together_go_to_the_earlier_pattern() {
+    assert_eq!(explained(&[], &["b+", "bb"], "abb"), ["a:TOKEN", "bb:SUFFIX#0"]);
+    assert_eq!(explained(&[], &["bb", "b+"], "abb"), ["a:TOKEN", "bb:SUFFIX#0"]);
+}
+
+#[test]
+fn the_first_matching_prefix_wins() {
+    assert_eq!(explained(&["a", "ab"], &[], "abc"), ["a:PREFIX#0", "bc:TOKEN"]);
+    assert_eq!(explained(&["ab", "a"], &[], "abc"), ["ab:PREFIX#0", "c:TOKEN"]);
+    assert_eq!(explained(&[r"[ab]", "ab"], &[], "abc"), ["a:PREFIX#0", "b:PREFIX#0", "c:TOKEN"]);
+}
+
+#[test]
+fn empty_matches_are_not_affixes() {
+    // An earlier prefix that matches empty hides later ones, as in spaCy's combined regex.
+    assert_eq!(explained(&[r"x*", "a"], &[], "abc"), ["abc:TOKEN"]);
+    assert_eq!(explained(&["a", r"x*"], &[], "abc"), ["a:PREFIX#0", "bc:TOKEN"]);
+    // A suffix that matches empty at the end loses to any that starts earlier.
+    assert_eq!(explained(&[], &[r"x*"], "ab"), ["ab:TOKEN"]);
+    assert_eq!(explained(&[], &[r"x*"], "abx"), ["ab:TOKEN", "x:SUFFIX#0"]);
+    assert_eq!(explained(&[], &[r"x*", "b"], "ab"), ["a:TOKEN", "b:SUFFIX#1"]);
+}
@@ -0,0 +1,56 @@
+// tests/borrowed.rs
+//! `tokenize_borrowed` must give the same tokens as `tokenize`, as slices of the input.
+
+use my_spacy_tokenizer::{Token, TokenRef, Tokenizer};
+
+const TEXTS: &[&str] = &[
+    "",
+    "Hello, world!",
+    "  \"Don't\" go -- it's 3.5km (2mi) away.\n\tSee https://example.com/a?b=1 or me@example.com :-)  ",
+    "naïve café e\u{301}tude 👩\u{200D}👩\u{200D}👧 𝐀b 東京 gimme can't U.S.A.",
+];
+
+fn assert_same_token(borrowed: &TokenRef, owned: &Token) {
+    assert_eq!(borrowed.text, owned.text);
+    assert_eq!((borrowed.start_byte, borrowed.end_byte), (owned.start_byte, owned.end_byte));
+    assert_eq!((borrowed.start_char, borrowed.end_char), (owned.start_char, owned.end_char));
+    assert_eq!((borrowed.start_utf16, borrowed.end_utf16), (owned.start_utf16, owned.end_utf16));
+    assert_eq!((borrowed.start_grapheme, borrowed.end_grapheme), (owned.start_grapheme, owned.end_grapheme));
+    assert_eq!(borrowed.whitespace_after, owned.whitespace_after);
+    assert_eq!(&borrowed.to_token(), owned);
+}
+
+/// Whether `token`'s text lies inside `text`, at the token's byte span, rather than in a copy.
+fn borrows_from(token: &TokenRef, text: &str) -> bool {
+    let input = text.as_bytes().as_ptr_range();
+    let token_range = token.text.as_bytes().as_ptr_range();
+    input.start <= token_range.start && token_range.end <= input.end
+        && token_range.start == text[token.start_byte..].as_ptr()
+}
+
+#[test]
+fn borrowed_tokens_equal_owned_tokens() {
+    let tokenizer = Tokenizer::english().unwrap();
+    for text in TEXTS {
+        let owned = tokenizer.tokenize(text).unwrap();
+        let borrowed = tokenizer.tokenize_borrowed(text).unwrap();
+        assert_eq!(borrowed.len(), owned.len(), "{:?}", text);
+        for (borrowed, owned) in borrowed.iter().zip(&owned) {
+            assert_same_token(borrowed, owned);
+        }
+    }
+}
+
+#[test]
+fn borrowed_tokens_point_into_the_input() {
+    let tokenizer = Tokenizer::english().unwrap();
+    for text in TEXTS {
+        for token in tokenizer.tokenize_borrowed(text).unwrap() {
+            assert!(borrows_from(&token, text), "{:?} is not a slice of {:?}", token.text, text);
+        }
+    }
+    let batch = tokenizer.tokenize_batch_borrowed(TEXTS).unwrap();
+    for (tokens, text) in batch.iter().zip(TEXTS) {
+        assert!(tokens.iter().all(|token| borrows_from(token, text)), "{:?}", text);
+    }
+}
@@ -0,0 +1,38 @@
+// tests/cache.rs
+//! The chunk cache keeps exactly the requested bound and never changes a tokenization.
+
+use my_spacy_tokenizer::Tokenizer;
+
+const TEXT: &str = "\"Hello,\" she said -- it's 3.5km (about 2mi) to the U.S. border!\n\
+    Don't go... it's late; she said \"Hello,\" again.\tE-mail me@example.com or visit https://example.com/a?b=1.\n\
+    The café's crème brûlée costs $4.50, doesn't it? 😀 :-) Hello, hello, HELLO!!";
+
+#[test]
+fn capacity_is_the_requested_bound() {
+    for capacity in [1, 2, 5, 15, 16, 17, 100, 1000] {
+        let tokenizer = Tokenizer::english().unwrap().with_chunk_cache(capacity);
+        for _ in 0..3 {
+            tokenizer.tokenize(TEXT).unwrap();
+        }
+        let stats = tokenizer.chunk_cache_stats().unwrap();
+        assert_eq!(stats.capacity, capacity);
+        assert!(stats.entries <= capacity, "{} entries for capacity {}", stats.entries, capacity);
+    }
+    assert_eq!(Tokenizer::english().unwrap().with_chunk_cache(0).chunk_cache_stats(), None);
+}
+
+#[test]
+fn cache_does_not_change_output() {
+    let uncached = Tokenizer::english().unwrap().without_chunk_cache();
+    let expected = uncached.tokenize(TEXT).unwrap();
+    // Small caches are emptied over and over; the large one is read from after the first pass.
+    for capacity in [1, 3, 16, 1024] {
+        let cached = Tokenizer::english().unwrap().with_chunk_cache(capacity);
+        for pass in 0..3 {
+            assert_eq!(cached.tokenize(TEXT).unwrap(), expected, "capacity {}, pass {}", capacity, pass);
+        }
+        if capacity == 1024 {
+            assert!(cached.chunk_cache_stats().unwrap().hits > 0);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+// tests/classify.rs
+//! Each pattern must be routed to the cheapest engine that can run it, and the report must
+//! say which one it got and why.
+
+use std::collections::HashMap;
+
+use my_spacy_tokenizer::{PatternClass, PatternClassification, RuleKind, Tokenizer, TokenizerRules};
+
+fn strings(patterns: &[&str]) -> Vec<String> {
+    patterns.iter().map(|p| p.to_string()).collect()
+}
+
+fn summary(p: &PatternClassification) -> (RuleKind, usize, &str, PatternClass, Option<&str>) {
+    (p.kind, p.index, p.pattern.as_str(), p.class, p.reason.as_deref())
+}
+
+#[test]
+fn patterns_are_routed_by_what_they_need() {
+    let rules = TokenizerRules::from_patterns(
+        &strings(&[r"\(", r"\$|€", r"[0-9]+", r"(?<=[0-9])k", r"(a)\1"]),
+        &strings(&[r"\.\.\.", r"(?i)km", r"x$", r"(?<!a)b"]),
+        &strings(&[",", r"[a-z]-[a-z]", r"(?<=[a-z])-(?=[a-z])"]),
+        &[],
+        None,
+        None,
+        HashMap::new(),
+    ).unwrap();
+    let report = rules.classification_report();
+    use PatternClass::*;
+    use RuleKind::*;
+    let expected = [
+        (Prefix, 0, r"\(", Literal, None),
+        (Prefix, 1, r"\$|€", Literal, None),
+        (Prefix, 2, r"[0-9]+", Regex, None),
+        (Prefix, 3, r"(?<=[0-9])k", Fancy, Some("look-around")),
+        (Prefix, 4, r"(a)\1", Fancy, Some("backreference")),
+        (Suffix, 0, r"\.\.\.", Literal, None),
+        (Suffix, 1, r"(?i)km", Literal, None),
+        // `$` is an assertion, which Aho-Corasick cannot check.
+        (Suffix, 2, r"x$", Regex, None),
+        (Suffix, 3, r"(?<!a)b", Fancy, Some("look-around")),
+        (Infix, 0, ",", Literal, None),
+        (Infix, 1, r"[a-z]-[a-z]", Regex, None),
+        (Infix, 2, r"(?<=[a-z])-(?=[a-z])", Fancy, Some("look-around")),
+    ];
+    assert_eq!(report.patterns.iter().map(summary).collect::<Vec<_>>(), expected);
+    assert_eq!(report.count(Prefix, Fancy), 2);
+    assert_eq!(report.fancy().count(), 4);
+
+    let tokens = Tokenizer::new(rules).tokenize("($12 5k x,y").unwrap();
+    let texts: Vec<&str> = tokens.iter().map(|t| t.as_str()).collect();
+    assert_eq!(texts, ["(", "$", "12", "5", "k", "x", ",", "y"]);
+}
+
+#[test]
+fn patterns_regex_rejects_fall_back_to_fancy() {
+    // `regex-syntax` accepts it, but it exceeds the `regex` crate's size limit.
+    let oversized = r"\w{250}";
+    let rules = TokenizerRules::from_patterns(&[], &[], &[], &[], Some(oversized), None, HashMap::new()).unwrap();
+    let report = rules.classification_report();
+    assert_eq!(report.patterns.len(), 1);
+    let p = &report.patterns[0];
+    assert_eq!((p.kind, p.class), (RuleKind::TokenMatch, PatternClass::Fancy));
+    assert!(p.reason.as_deref().is_some_and(|r| r.starts_with("rejected by regex")), "{:?}", p.reason);
+
+    let word = "a".repeat(250);
+    let tokens = Tokenizer::new(rules).with_fast_path(false).tokenize(&format!("{}.", word)).unwrap();
+    assert_eq!(tokens.len(), 1);
+}
+
+#[test]
+fn english_report_covers_every_pattern() {
+    let rules = TokenizerRules::new().unwrap();
+    let report = rules.classification_report();
+    let total = |kind| [PatternClass::Literal, PatternClass::Regex, PatternClass::Fancy].iter().map(|&c| report.count(kind, c)).sum::<usize>();
+    assert_eq!(total(RuleKind::Prefix), rules.num_prefix_patterns());
+    assert_eq!(total(RuleKind::Suffix), rules.num_suffix_patterns());
+    assert_eq!(total(RuleKind::Infix), rules.num_regex_infix_patterns());
+    assert!(report.fancy().all(|p| p.reason.is_some()));
+    assert!(report.patterns.iter().filter(|p| p.class != PatternClass::Fancy).all(|p| p.reason.is_none()));
+}
@@ -0,0 +1,14 @@
+// tests/common/mod.rs
+//! Helpers shared by the integration tests.
+
+/// A small deterministic generator, so failures are reproducible without extra dependencies.
+pub struct XorShift(pub u64);
+
+impl XorShift {
+    pub fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+}
@@ -0,0 +1,75 @@
+// tests/config.rs
+//! Rule sets saved as JSON or TOML must load back into the same tokenizer.
+
+use std::fs;
+use std::path::PathBuf;
+
+use my_spacy_tokenizer::{ConfigFormat, RulesConfig, Tokenizer, TokenizerError, TokenizerRules};
+
+const TEXT: &str = "\"Hello,\" she said -- it's 3.5km (about 2mi) to the U.S. border!\n\
+    Don't go... e-mail me@example.com or visit https://example.com/a?b=1. :-) 10%";
+
+/// A path in the temp dir unique to this test process.
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("my_spacy_tokenizer_{}_{}", std::process::id(), name))
+}
+
+#[test]
+fn english_config_round_trips_through_json_and_toml() {
+    let english = RulesConfig::english();
+    let expected = Tokenizer::english().unwrap().tokenize(TEXT).unwrap();
+    for name in ["rules.json", "rules.toml"] {
+        let path = temp_path(name);
+        english.save(&path).unwrap();
+        let loaded = RulesConfig::from_path(&path);
+        let rules = TokenizerRules::from_config(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.unwrap(), english, "{}", name);
+        let tokens = Tokenizer::new(rules.unwrap()).tokenize(TEXT).unwrap();
+        assert_eq!(tokens, expected, "{}", name);
+    }
+}
+
+#[test]
+fn strings_round_trip_in_both_formats() {
+    let english = RulesConfig::english();
+    for format in [ConfigFormat::Json, ConfigFormat::Toml] {
+        let content = english.to_string_with_format(format).unwrap();
+        assert_eq!(RulesConfig::from_str_with_format(&content, format).unwrap(), english, "{:?}", format);
+    }
+}
+
+#[test]
+fn unknown_extensions_are_rejected() {
+    for name in ["rules.yaml", "rules"] {
+        let path = temp_path(name);
+        assert!(matches!(RulesConfig::english().save(&path), Err(TokenizerError::InvalidConfig(_))), "{}", name);
+        assert!(!path.exists());
+        assert!(matches!(TokenizerRules::from_config(&path), Err(TokenizerError::InvalidConfig(_))), "{}", name);
+    }
+}
+
+#[test]
+fn malformed_files_are_invalid_configs() {
+    for (name, content) in [
+        ("broken.json", "{\"prefixes\": [\"a\""),
+        ("typed.json", "{\"prefixes\": 3}"),
+        ("broken.toml", "prefixes = [\"a\""),
+        ("typed.toml", "suffixes = \"not a list\""),
+    ] {
+        let path = temp_path(name);
+        fs::write(&path, content).unwrap();
+        let result = TokenizerRules::from_config(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(result, Err(TokenizerError::InvalidConfig(_))), "{}", name);
+    }
+}
+
+#[test]
+fn missing_fields_default_to_empty() {
+    let config = RulesConfig::from_str_with_format("prefixes = [\"\\\\(\"]", ConfigFormat::Toml).unwrap();
+    assert_eq!(config, RulesConfig { prefixes: vec![r"\(".to_string()], ..RulesConfig::default() });
+    let tokens = Tokenizer::new(config.compile().unwrap()).tokenize("(a").unwrap();
+    assert_eq!(tokens.iter().map(|t| t.as_str()).collect::<Vec<_>>(), ["(", "a"]);
+}
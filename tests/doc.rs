@@ -0,0 +1,36 @@
+// tests/doc.rs
+//! The tokens of a `Doc` and their trailing spaces must rebuild the input exactly.
+
+use my_spacy_tokenizer::Tokenizer;
+
+#[test]
+fn text_with_ws_round_trips_whitespace() {
+    let tokenizer = Tokenizer::english().unwrap();
+    let texts = [
+        "",
+        " ",
+        "Hello world.",
+        "Hello  world",
+        "tab\tseparated\tvalues",
+        "line one\nline two\n",
+        "\r\nWindows\r\nlines\r\n",
+        "   leading spaces",
+        "trailing spaces   ",
+        "\t\n mixed \n\t runs \t\n",
+        " a\t\tb \n\n c ",
+        "no-break\u{a0}space and\u{2003}em space",
+    ];
+    for text in texts {
+        let doc = tokenizer.make_doc(text).unwrap();
+        assert_eq!(doc.text_with_ws(), text, "tokens {:?}", doc.tokens());
+        assert_eq!(doc.text(), text);
+    }
+}
+
+#[test]
+fn extra_whitespace_becomes_tokens() {
+    let tokenizer = Tokenizer::english().unwrap();
+    let doc = tokenizer.make_doc("Hello  world\n").unwrap();
+    let tokens: Vec<(&str, bool)> = doc.iter().map(|t| (t.text.as_str(), t.whitespace_after)).collect();
+    assert_eq!(tokens, [("Hello", true), (" ", false), ("world", false), ("\n", false)]);
+}
@@ -0,0 +1,103 @@
+// tests/explain.rs
+//! `Tokenizer::explain` must name the rule, and the pattern within it, behind each token.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use my_spacy_tokenizer::{CollectTraceSink, RegexErrorPolicy, RuleKind, TokenRule, Tokenizer, TokenizerRules};
+
+fn strings(patterns: &[&str]) -> Vec<String> {
+    patterns.iter().map(|p| p.to_string()).collect()
+}
+
+/// A rule set where each rule, and each pattern within a rule list, is easy to trigger.
+fn tokenizer() -> Tokenizer {
+    let piece = |orth: &str| HashMap::from([("ORTH".to_string(), orth.to_string())]);
+    let exceptions = HashMap::from([("can't".to_string(), vec![piece("ca"), piece("n't")])]);
+    let rules = TokenizerRules::from_patterns(
+        &strings(&[r"\(", r"\$", "e"]),
+        &strings(&[r"\)", "%", r"\."]),
+        &strings(&[r"(?<=[a-z])-(?=[a-z])"]),
+        &strings(&["--"]),
+        Some(r"^\d+(?:\.\d+)?$"),
+        Some(r"^https?://\S+$"),
+        exceptions,
+    ).unwrap();
+    Tokenizer::new(rules)
+}
+
+fn explained(tokenizer: &Tokenizer, text: &str) -> Vec<(String, String, Option<usize>)> {
+    tokenizer.explain(text).unwrap().into_iter()
+        .map(|e| (e.text, e.rule.name().into_owned(), e.rule.pattern_index()))
+        .collect()
+}
+
+fn row(text: &str, name: &str, index: Option<usize>) -> (String, String, Option<usize>) {
+    (text.to_string(), name.to_string(), index)
+}
+
+#[test]
+fn labels_and_pattern_indices_name_the_rule_that_fired() {
+    let tokenizer = tokenizer();
+    assert_eq!(explained(&tokenizer, "($5%) can't well-known a--b 3.14 http://x.org (x)."), [
+        row("(", "PREFIX", Some(0)),
+        row("$", "PREFIX", Some(1)),
+        row("5", "TOKEN", None),
+        row("%", "SUFFIX", Some(1)),
+        row(")", "SUFFIX", Some(0)),
+        row("ca", "SPECIAL-1", None),
+        row("n't", "SPECIAL-2", None),
+        row("well", "TOKEN", None),
+        row("-", "INFIX", Some(0)),
+        row("known", "TOKEN", None),
+        row("a", "TOKEN", None),
+        row("--", "INFIX", Some(0)),
+        row("b", "TOKEN", None),
+        row("3.14", "TOKEN_MATCH", None),
+        row("http://x.org", "URL_MATCH", None),
+        row("(", "PREFIX", Some(0)),
+        row("x", "TOKEN", None),
+        row(")", "SUFFIX", Some(0)),
+        row(".", "SUFFIX", Some(2)),
+    ]);
+    // Regex and literal infixes share the spaCy label but not the index space.
+    let rules: Vec<TokenRule> = tokenizer.explain("well-known a--b").unwrap().into_iter().map(|e| e.rule).collect();
+    assert_eq!(rules[1], TokenRule::Infix(0));
+    assert_eq!(rules[4], TokenRule::LiteralInfix(0));
+    assert_eq!(rules[1].to_string(), "INFIX#0");
+    assert_eq!(TokenRule::Special(2).to_string(), "SPECIAL-2");
+}
+
+#[test]
+fn merged_grapheme_clusters_keep_the_rule_of_their_first_piece() {
+    // The prefix `e` would split `e` from its combining accent; the pieces are merged back.
+    assert_eq!(explained(&tokenizer(), "e\u{301}x ex"), [
+        row("e\u{301}x", "PREFIX", Some(2)),
+        row("e", "PREFIX", Some(2)),
+        row("x", "TOKEN", None),
+    ]);
+}
+
+#[test]
+fn explain_traces_chunks_at_their_offsets() {
+    let sink = Arc::new(CollectTraceSink::new());
+    let tokenizer = tokenizer().with_trace_sink(sink.clone());
+    tokenizer.explain("hello world (x)").unwrap();
+    drop(tokenizer);
+    let events = Arc::try_unwrap(sink).unwrap().into_events();
+    let chunks: Vec<(&str, usize)> = events.iter().map(|e| (e.chunk.as_str(), e.chunk_start_char)).collect();
+    // `hello` and `world`: chunk and fallback; `(x)`: chunk, prefix and suffix.
+    assert_eq!(chunks, [("hello", 0), ("hello", 0), ("world", 6), ("world", 6), ("(x)", 12), ("(x)", 12), ("(x)", 12)]);
+}
+
+#[test]
+fn explain_with_report_returns_skipped_rules() {
+    let rules = TokenizerRules::from_patterns(&strings(&[r"(a|aa)+(?=\1)b"]), &[], &[], &[], None, None, HashMap::new()).unwrap();
+    let tokenizer = Tokenizer::new(rules).with_regex_error_policy(RegexErrorPolicy::WarnAndFallback);
+    let text = format!("ok {} ok", "a".repeat(40));
+    let (explanations, errors) = tokenizer.explain_with_report(&text).unwrap();
+    assert_eq!(explanations.len(), 3);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, RuleKind::Prefix);
+    assert_eq!(errors[0].chunk_start_char, 3);
+}
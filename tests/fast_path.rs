@@ -0,0 +1,99 @@
+// tests/fast_path.rs
+//! Differential test: the plain-chunk fast path must not change any tokenization.
+
+mod common;
+
+use std::collections::HashMap;
+
+use common::XorShift;
+use my_spacy_tokenizer::{Token, Tokenizer, TokenizerRules};
+
+/// Pieces the generated chunks are built from: ASCII and non-ASCII letters (including the
+/// letters of letter-only emoticons such as `xD`), combining marks, digits, punctuation and
+/// symbols the prefix/suffix/infix rules react to.
+const PIECES: &[&str] = &[
+    "a", "e", "s", "t", "D", "x", "X", "xD", "XD", "xDD", "é", "ß", "ø", "Ω", "я", "東", "京", "ا",
+    "e\u{301}", "\u{308}", "1", "42", "3.5", ".", ",", "'", "'s", "n't", "-", "–", "/", "(", ")", "\"",
+    "“", "”", "$", "€", "%", "km", "!", "?", "...", ":", ";", ")", ":-)", "@", "#", "&", "_", "😀",
+];
+
+fn generated_text(seed: u64, num_chunks: usize) -> String {
+    let mut rng = XorShift(seed);
+    let mut text = String::new();
+    for i in 0..num_chunks {
+        if i > 0 {
+            text.push_str(if rng.below(10) == 0 { "\n" } else { " " });
+        }
+        // Mostly letter runs, as in real text, with a mix of everything else.
+        let letters_only = rng.below(2) == 0;
+        for _ in 0..1 + rng.below(5) {
+            let piece = loop {
+                let piece = PIECES[rng.below(PIECES.len())];
+                if !letters_only || piece.chars().all(char::is_alphabetic) {
+                    break piece;
+                }
+            };
+            text.push_str(piece);
+        }
+    }
+    text
+}
+
+fn slow_and_fast(text: &str) -> (Vec<Token>, Vec<Token>) {
+    let tokenizer = Tokenizer::english().unwrap().without_chunk_cache();
+    let slow = tokenizer.clone().with_fast_path(false).tokenize(text).unwrap();
+    let fast = tokenizer.with_fast_path(true).tokenize(text).unwrap();
+    (slow, fast)
+}
+
+#[test]
+fn english_rules_enable_the_fast_path() {
+    assert!(TokenizerRules::new().unwrap().has_plain_chunk_fast_path());
+}
+
+#[test]
+fn fast_path_matches_slow_path_on_generated_text() {
+    for seed in 1..=10 {
+        let text = generated_text(seed, 1_000);
+        let (slow, fast) = slow_and_fast(&text);
+        assert_eq!(slow, fast, "seed {}", seed);
+    }
+}
+
+#[test]
+fn fast_path_matches_slow_path_on_sample_sentences() {
+    let text = "The quick brown fox jumps over the lazy dog.\n\
+        Straße naïve café Ωmega проверка 東京 مرحبا e\u{301}tude\n\
+        xD XD lolxD foxDD Xerox Mexico exactly relax\n\
+        gonna can't don't Dr. U.S.A. e.g. 3km 10% $5 (hello) \"quoted\" end.";
+    let (slow, fast) = slow_and_fast(text);
+    assert_eq!(slow, fast);
+}
+
+#[test]
+fn letter_only_rule_matches_still_split() {
+    // `xD` is a letter-only emoticon suffix, so chunks with an `x` must not take the fast path.
+    let tokenizer = Tokenizer::english().unwrap().without_chunk_cache();
+    let texts: Vec<String> = tokenizer.tokenize("lolxD").unwrap().into_iter().map(|t| t.text).collect();
+    assert_eq!(texts, ["lol", "xD"]);
+}
+
+/// Checks that `prefixes` and `infixes` split `text` into `expected`, with and without the fast path.
+fn assert_splits(prefixes: &[&str], infixes: &[&str], text: &str, expected: &[&str]) {
+    let strings = |patterns: &[&str]| patterns.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+    let rules = TokenizerRules::from_patterns(&strings(prefixes), &[], &strings(infixes), &[], None, None, HashMap::new()).unwrap();
+    let tokenizer = Tokenizer::new(rules).without_chunk_cache();
+    for fast_path in [false, true] {
+        let tokens = tokenizer.clone().with_fast_path(fast_path).tokenize(text).unwrap();
+        let texts: Vec<&str> = tokens.iter().map(|t| t.as_str()).collect();
+        assert_eq!(texts, expected, "{:?}, fast path {}", text, fast_path);
+    }
+}
+
+#[test]
+fn look_around_on_one_alternative_does_not_guard_the_others() {
+    // The look-ahead/look-behind only applies to one side of the `|`; `ab` and `q` still match
+    // inside letter runs.
+    assert_splits(&[], &[r"ab|c(?=[0-9])"], "xaby", &["x", "ab", "y"]);
+    assert_splits(&[r"(?<=[0-9])k|q"], &[], "qwe", &["q", "we"]);
+}
@@ -0,0 +1,88 @@
+"""Regenerates the SHAPE fixtures checked by tests/shape.rs from spaCy's own `word_shape`.
+
+    pip install spacy
+    python3 tests/fixtures/gen_shapes.py
+
+The spaCy version is recorded in the headers. `word_shape` only relies on Python's `str`
+predicates, so the Unicode version of the Python build matters too: it is recorded as well,
+and the per-char fixture only lists chars assigned in that version.
+"""
+import sys
+import unicodedata
+from pathlib import Path
+
+try:
+    import spacy
+    from spacy.lang.lex_attrs import word_shape
+except ImportError:
+    sys.exit("gen_shapes.py needs spaCy installed (pip install spacy)")
+
+SOURCE = "spaCy {} (spacy.lang.lex_attrs.word_shape)".format(spacy.__version__)
+
+
+HERE = Path(__file__).parent
+HEADER = "# Generated by gen_shapes.py with {} on Python {} (Unicode {}).\n".format(
+    SOURCE, sys.version.split()[0], unicodedata.unidata_version
+)
+
+
+WORDS = [
+    # Latin, with case, digits and punctuation
+    "a", "A", "the", "The", "THE", "Apple", "iPhone", "McDonald", "don't", "n't", "'s", "U.S.A.",
+    "e.g.", "Mr.", "C++", "C#", "x86_64", "COVID-19", "3D", "mp3", "F1", "B2B",
+    "aaaa", "aaaaa", "aaaaaaaaaa", "AAAAA", "AaAaAa", "aaaaAAAA", "aaaaAAAAaaaa",
+    "1", "12", "1234", "12345", "1234567890", "1,000", "1,000,000", "3.14", "0.001", "-5", "+5",
+    "3/4", "1st", "2nd", "21st", "10:30", "2024-01-01", "$5.00", "€10", "£", "50%", "#1", "@user",
+    "me@example.com", "john.doe@mail.co.uk", "http://example.com", "https://www.example.com/a/b?c=d",
+    "www.google.com", "google.com",
+    ".", ",", "...", "....", ".....", "!!!!!!", "?!", "--", "-----", "***", "(", ")", "[]", "{}", "<3",
+    ":)", ":-)", "xD", "XD", "^_^", "o_O", "\\", "/", "¯\\_(ツ)_/¯", "'", "\"", "“", "”", "‘", "’",
+    # Letters beyond ASCII
+    "café", "naïve", "Straße", "STRASSE", "Ærøskøbing", "Łódź", "İstanbul", "ǅungla", "ǈ", "ǋ",
+    "Ωmega", "αβγδεζ", "ΑΒΓΔΕΖ", "Москва", "МОСКВА", "привет", "Україна", "Ꭰ", "ꭰ",
+    "東京", "東京都庁舎", "ひらがな", "カタカナ", "ｶﾀｶﾅ", "한국어", "ㄱㄴ", "ไทย", "עברית", "مرحبا",
+    "हिन्दी", "বাংলা", "தமிழ்", "ქართული", "Հայերեն", "ʻokina", "ʼ", "ˈstress", "ª", "º",
+    "e\u0301", "cafe\u0301", "a\u0308b", "ﬁ", "ﬂow", "Ⅻ", "ⅻ", "ⓐⓑ", "Ⓐ", "𝐀𝐁𝐂", "𝐚𝐛𝐜", "𝔄𝔅",
+    # Digits beyond ASCII
+    "٠١٢٣", "۱۲۳", "१२३", "১২৩", "๑๒๓", "０１２３", "１２３４５", "²", "x²", "10³", "H₂O", "½", "¾", "⅓",
+    "①②", "⑴", "⒈", "❶", "➀", "፩፪", "𝟘𝟙𝟚", "〇", "一二三", "Ⅳ",
+    # Symbols, marks, emoji and whitespace
+    "©", "®", "™", "°C", "±", "×", "÷", "→", "✓", "♥", "😀", "😀😀😀😀😀", "👍🏽", "👨\u200d👩\u200d👧", "🇺🇸",
+    "\u00a0", " ", "  ", "\u200b", "\u0301", "\ufeff",
+    # Length cap
+    "a" * 99, "a" * 100, "A" * 150, "ab" * 49 + "A", "ab" * 50,
+]
+
+
+def char_classes():
+    """Runs of consecutive assigned code points with the same shape class: X, x, d or
+    `-` (the char is its own shape char)."""
+    runs = []
+    for cp in range(sys.maxunicode + 1):
+        c = chr(cp)
+        if unicodedata.category(c) in ("Cn", "Cs"):
+            continue
+        shape = word_shape(c)
+        cls = shape if shape in ("X", "x", "d") else "-"
+        if runs and runs[-1][1] == cp - 1 and runs[-1][2] == cls:
+            runs[-1][1] = cp
+        else:
+            runs.append([cp, cp, cls])
+    return runs
+
+
+def main():
+    with open(HERE / "shape_chars.tsv", "w", encoding="utf-8") as f:
+        f.write(HEADER)
+        f.write("# first\tlast\tclass (hex code points; class X, x, d, or - for the char itself)\n")
+        for first, last, cls in char_classes():
+            f.write("{:X}\t{:X}\t{}\n".format(first, last, cls))
+
+    with open(HERE / "shape_words.tsv", "w", encoding="utf-8") as f:
+        f.write(HEADER)
+        f.write("# text\tshape\n")
+        for word in WORDS:
+            f.write("{}\t{}\n".format(word, word_shape(word)))
+
+if __name__ == "__main__":
+    main()
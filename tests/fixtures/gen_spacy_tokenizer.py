@@ -0,0 +1,38 @@
+"""Regenerates spacy_tokenizer.msgpack, checked by tests/spacy_import.rs.
+
+    python3 tests/fixtures/gen_spacy_tokenizer.py
+
+Builds a small spaCy tokenizer and writes `Tokenizer.to_bytes(exclude=["vocab"])`, the same
+msgpack map `nlp.tokenizer.to_disk()` writes: Python `re` patterns combined the way spaCy's
+`compile_*_regex` helpers do, and special cases keyed by attribute ids (ORTH=65, NORM=67).
+Requires spaCy.
+"""
+import re
+from pathlib import Path
+
+import spacy
+from spacy.attrs import NORM, ORTH
+from spacy.tokenizer import Tokenizer
+from spacy.util import compile_infix_regex, compile_prefix_regex, compile_suffix_regex
+
+PREFIXES = [r"\$", r"\(", r"\N{EM DASH}", "[\"'|]", r"(?:\.\.\.|…)"]
+SUFFIXES = [r"\)", r"\.", r"(?<=[0-9])km"]
+INFIXES = [r"(?<=[a-z])[,!?](?=[a-z])", r"(?<=[0-9])[+\-*^](?=[0-9-])"]
+URL_MATCH = r"^https?://\S+\Z"
+SPECIAL_CASES = {
+    "don't": [{ORTH: "do"}, {ORTH: "n't", NORM: "not"}],
+    "e.g.": [{ORTH: "e.g."}],
+}
+
+nlp = spacy.blank("en")
+tokenizer = Tokenizer(
+    nlp.vocab,
+    rules=SPECIAL_CASES,
+    prefix_search=compile_prefix_regex(PREFIXES).search,
+    suffix_search=compile_suffix_regex(SUFFIXES).search,
+    infix_finditer=compile_infix_regex(INFIXES).finditer,
+    url_match=re.compile(URL_MATCH).match,
+)
+out = Path(__file__).with_name("spacy_tokenizer.msgpack")
+out.write_bytes(tokenizer.to_bytes(exclude=["vocab"]))
+print(f"wrote {out} with spacy {spacy.__version__}")
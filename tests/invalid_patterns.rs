@@ -0,0 +1,37 @@
+// tests/invalid_patterns.rs
+//! A pattern that does not compile is reported as `TokenizerError::InvalidPattern`, never a panic.
+
+use std::collections::HashMap;
+
+use my_spacy_tokenizer::{RuleKind, TokenizerError, TokenizerRules};
+
+fn strings(patterns: &[&str]) -> Vec<String> {
+    patterns.iter().map(|p| p.to_string()).collect()
+}
+
+fn assert_invalid(result: Result<TokenizerRules, TokenizerError>, expected_kind: RuleKind, expected_index: usize) {
+    match result {
+        Err(TokenizerError::InvalidPattern { kind, index, pattern, .. }) => {
+            assert_eq!((kind, index, pattern.as_str()), (expected_kind, expected_index, "("));
+        }
+        Err(e) => panic!("expected an invalid {} pattern, got {}", expected_kind, e),
+        Ok(_) => panic!("expected an invalid {} pattern, got rules", expected_kind),
+    }
+}
+
+#[test]
+fn invalid_patterns_name_their_rule_and_index() {
+    let list = strings(&["a", "("]);
+    assert_invalid(TokenizerRules::from_patterns(&list, &[], &[], &[], None, None, HashMap::new()), RuleKind::Prefix, 1);
+    assert_invalid(TokenizerRules::from_patterns(&[], &list, &[], &[], None, None, HashMap::new()), RuleKind::Suffix, 1);
+    assert_invalid(TokenizerRules::from_patterns(&[], &[], &list, &[], None, None, HashMap::new()), RuleKind::Infix, 1);
+    assert_invalid(TokenizerRules::from_patterns(&[], &[], &[], &[], Some("("), None, HashMap::new()), RuleKind::TokenMatch, 0);
+    assert_invalid(TokenizerRules::from_patterns(&[], &[], &[], &[], None, Some("("), HashMap::new()), RuleKind::UrlMatch, 0);
+}
+
+#[test]
+fn invalid_pattern_message_names_the_pattern() {
+    let err = TokenizerRules::from_patterns(&[], &strings(&["x", "y", "[a-"]), &[], &[], None, None, HashMap::new()).err().unwrap();
+    assert!(err.to_string().starts_with("invalid suffix pattern #2 '[a-'"), "{}", err);
+    assert!(std::error::Error::source(&err).is_some());
+}
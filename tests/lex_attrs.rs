@@ -0,0 +1,91 @@
+// tests/lex_attrs.rs
+//! The character-class attributes against spaCy's `lex_attrs.py`: `IS_QUOTE`, `IS_BRACKET`,
+//! `IS_LEFT_PUNCT`, `IS_RIGHT_PUNCT` (membership in spaCy's tuples), `IS_TITLE`, `IS_SPACE`
+//! (Python's `str.istitle` and `str.isspace`), `LIKE_URL` and `LIKE_EMAIL`.
+
+use my_spacy_tokenizer::{AttrValue, LexAttrs, Symbol};
+
+fn check(name: Symbol, yes: &[&str], no: &[&str]) {
+    let table = LexAttrs::default_table();
+    for text in yes {
+        assert_eq!(table.get(text, name), Some(AttrValue::Bool(true)), "{:?} should be {:?}", text, name);
+    }
+    for text in no {
+        assert_eq!(table.get(text, name), Some(AttrValue::Bool(false)), "{:?} should not be {:?}", text, name);
+    }
+}
+
+#[test]
+fn is_quote_matches_spacys_quote_list() {
+    check(
+        Symbol::IS_QUOTE,
+        &["\"", "'", "`", "«", "»", "‘", "’", "‚", "‛", "“", "”", "„", "‟", "‹", "›", "❮", "❯", "''", "``"],
+        &["", "a", "(", "\"\"", "'''", "「", "」", "〝", "＂", "'a'"],
+    );
+}
+
+#[test]
+fn is_bracket_is_ascii_only() {
+    check(
+        Symbol::IS_BRACKET,
+        &["(", ")", "[", "]", "{", "}", "<", ">"],
+        &["", "()", "（", "）", "【", "】", "「", "⟨", "«", "\""],
+    );
+}
+
+#[test]
+fn is_left_punct_matches_spacys_list() {
+    check(
+        Symbol::IS_LEFT_PUNCT,
+        &["(", "[", "{", "<", "\"", "'", "«", "‘", "‚", "‛", "“", "„", "‟", "‹", "❮", "``"],
+        &["", ")", "]", "»", "’", "”", "›", "❯", "''", "`", "（", "「"],
+    );
+}
+
+#[test]
+fn is_right_punct_matches_spacys_list() {
+    check(
+        Symbol::IS_RIGHT_PUNCT,
+        &[")", "]", "}", ">", "\"", "'", "»", "’", "”", "›", "❯", "''"],
+        &["", "(", "[", "«", "‘", "“", "„", "‹", "❮", "``", "）", "」"],
+    );
+}
+
+#[test]
+fn is_title_follows_python_istitle() {
+    check(
+        Symbol::IS_TITLE,
+        &["Hello", "A", "Hello World", "Élan", "ǅungla", "3D", "Hello-World", "Ünïcode"],
+        &["", "hello", "HELLO", "HeLLo", "Hello world", "Don't", "123", "-", "A1b"],
+    );
+}
+
+#[test]
+fn is_space_follows_python_isspace() {
+    check(
+        Symbol::IS_SPACE,
+        &[" ", "  ", "\t\n", "\r\n", "\u{a0}", "\u{3000}", "\u{2028}", "\u{85}", "\u{1c}", "\u{1f}"],
+        &["", "a", " a ", "\u{200b}", "\u{feff}", "_"],
+    );
+}
+
+#[test]
+fn like_url_accepts_schemes_www_and_known_tlds() {
+    check(
+        Symbol::LIKE_URL,
+        &[
+            "http://x", "https://example.com/path", "www.a", "www.google.com", "google.com", "example.co.uk",
+            "example.com:8080", "example.org/", "spacy.io/usage",
+        ],
+        &["", "www.", "hello", "hello.", ".com", "me@example.com", "a.b", "..."],
+    );
+}
+
+#[test]
+fn like_email_matches_at_the_start() {
+    check(
+        Symbol::LIKE_EMAIL,
+        &["me@example.com", "john.doe+tag@mail.co.uk", "a_b-c@x-y.org", "me@example.com."],
+        &["", "@example.com", "me@localhost", "me@", "me example@x.com", "me@.com"],
+    );
+}
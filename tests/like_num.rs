@@ -0,0 +1,44 @@
+// tests/like_num.rs
+//! `LIKE_NUM` as spaCy's English `like_num`: digits with separators, fractions, ordinals and
+//! number words.
+
+use my_spacy_tokenizer::lex_attrs::{english_like_num, like_num};
+use my_spacy_tokenizer::{AttrValue, LexAttrs, Symbol};
+
+const NUMBERS: &[&str] = &[
+    "0", "42", "1,000", "1,000,000", "3.14", "-5", "+5", "±1", "~10", "3/4", "10/20",
+    "21st", "1st", "2nd", "3rd", "100th", "twenty", "Twenty", "million", "MILLION", "first", "twentieth",
+];
+
+const NOT_NUMBERS: &[&str] = &[
+    "", "-", "/", "3/", "/4", "1/2/3", "3/4x", "abc", "x1", "21sts", "st", "twentieth-ish", "twenty-one",
+    "millions", "one2", "1e5",
+];
+
+#[test]
+fn english_like_num_accepts_numbers_ordinals_and_number_words() {
+    for text in NUMBERS {
+        assert!(english_like_num(text), "{:?} should be like a number", text);
+    }
+    for text in NOT_NUMBERS {
+        assert!(!english_like_num(text), "{:?} should not be like a number", text);
+    }
+}
+
+#[test]
+fn default_like_num_only_accepts_digits_and_fractions() {
+    for text in ["42", "1,000", "-5", "3/4"] {
+        assert!(like_num(text), "{:?}", text);
+    }
+    for text in ["21st", "twenty", "million", "first"] {
+        assert!(!like_num(text), "{:?}", text);
+    }
+}
+
+#[test]
+fn english_table_uses_english_like_num() {
+    let english = LexAttrs::english();
+    for (text, expected) in [("21st", true), ("3/4", true), ("1,000", true), ("twenty", true), ("million", true), ("-5", true), ("twentieth-ish", false)] {
+        assert_eq!(english.get(text, Symbol::LIKE_NUM), Some(AttrValue::Bool(expected)), "{:?}", text);
+    }
+}
@@ -0,0 +1,96 @@
+// tests/offsets.rs
+//! Token offsets, tracked in one walk over the text, must agree with recounting the input.
+
+use std::ops::Range;
+
+use my_spacy_tokenizer::{OffsetUnit, Token, Tokenizer};
+
+/// The byte, char and UTF-16 offsets of `token`, recounted from `text`.
+fn recounted(text: &str, token: &Token) -> [(usize, usize); 3] {
+    let before = &text[..token.start_byte];
+    let inner = &text[token.start_byte..token.end_byte];
+    let count = |s: &str| (s.chars().count(), s.encode_utf16().count());
+    let ((char_start, utf16_start), (chars, utf16s)) = (count(before), count(inner));
+    [
+        (token.start_byte, token.end_byte),
+        (char_start, char_start + chars),
+        (utf16_start, utf16_start + utf16s),
+    ]
+}
+
+fn spans(token: &Token) -> [(usize, usize); 3] {
+    [OffsetUnit::Byte, OffsetUnit::Char, OffsetUnit::Utf16].map(|unit| {
+        let span = token.span(unit);
+        (span.start, span.end)
+    })
+}
+
+#[test]
+fn offsets_match_recounting() {
+    let tokenizer = Tokenizer::english().unwrap();
+    let texts = [
+        "Hello, world!",
+        "  leading and trailing  ",
+        "naïve café — \"quoted\" (x) 3.5km\r\nnext\tline",
+        "𝐀𝐁𝐂 emoji 😀😀, math 𝑥=1; 東京 ok.",
+        "e\u{301}tude 👩\u{200D}👩\u{200D}👧 a \u{301}b",
+    ];
+    for text in texts {
+        let tokens = tokenizer.tokenize(text).unwrap();
+        for token in &tokens {
+            assert_eq!(&text[token.start_byte..token.end_byte], token.text, "{:?}", text);
+            assert_eq!(spans(token), recounted(text, token), "{:?} in {:?}", token.text, text);
+        }
+    }
+}
+
+#[test]
+fn astral_chars_take_two_utf16_units() {
+    let tokenizer = Tokenizer::english().unwrap();
+    let tokens = tokenizer.tokenize("👩\u{200D}👩\u{200D}👧  𝐀b").unwrap();
+    let token = tokens.last().unwrap();
+    assert_eq!(token.text, "𝐀b");
+    assert_eq!(token.span(OffsetUnit::Byte), 20..25);
+    assert_eq!(token.span(OffsetUnit::Char), 7..9);
+    assert_eq!(token.span(OffsetUnit::Utf16), 10..13);
+}
+
+/// The texts of `text`'s tokens, with their grapheme spans.
+fn grapheme_spans(text: &str) -> Vec<(String, Range<usize>)> {
+    let tokenizer = Tokenizer::english().unwrap();
+    tokenizer.tokenize(text).unwrap().into_iter().map(|t| {
+        let span = t.span(OffsetUnit::Grapheme);
+        (t.text, span)
+    }).collect()
+}
+
+#[test]
+fn grapheme_offsets_count_clusters() {
+    assert_eq!(grapheme_spans("👩\u{200D}👩\u{200D}👧  𝐀b"), [
+        ("👩\u{200D}👩\u{200D}👧".to_string(), 0..1),
+        (" ".to_string(), 2..3),
+        ("𝐀b".to_string(), 3..5),
+    ]);
+}
+
+#[test]
+fn zwj_sequences_are_never_split() {
+    // The brackets are split off by the prefix and suffix rules; the family stays one token.
+    let family = "👩\u{200D}👩\u{200D}👧\u{200D}👦";
+    assert_eq!(grapheme_spans(&format!("({}) ok", family)), [
+        ("(".to_string(), 0..1),
+        (family.to_string(), 1..2),
+        (")".to_string(), 2..3),
+        ("ok".to_string(), 4..6),
+    ]);
+}
+
+#[test]
+fn combining_mark_after_a_space_stays_with_the_space() {
+    // The space and the mark form one grapheme cluster, so no boundary falls between them.
+    assert_eq!(grapheme_spans("a \u{301}b"), [
+        ("a".to_string(), 0..1),
+        (" \u{301}".to_string(), 1..2),
+        ("b".to_string(), 2..3),
+    ]);
+}
@@ -0,0 +1,82 @@
+// tests/regex_errors.rs
+//! Rules that fail at match time are handled by the `RegexErrorPolicy`, never printed.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use my_spacy_tokenizer::{RegexErrorPolicy, RuleError, RuleKind, TokenizerError, TraceEvent, TraceSink, Tokenizer, TokenizerRules};
+
+/// A prefix that exceeds `fancy_regex`'s backtrack limit on a long run of `a`s.
+const RUNAWAY_PREFIX: &str = r"(a|aa)+(?=\1)b";
+
+fn runaway_tokenizer(policy: RegexErrorPolicy) -> Tokenizer {
+    let rules = TokenizerRules::from_patterns(&[RUNAWAY_PREFIX.to_string()], &[], &[], &[], None, None, HashMap::new()).unwrap();
+    Tokenizer::new(rules).with_regex_error_policy(policy)
+}
+
+fn runaway_text() -> String {
+    format!("ok {} ok", "a".repeat(40))
+}
+
+#[derive(Default)]
+struct WarningSink {
+    warnings: Mutex<Vec<RuleError>>,
+}
+
+impl TraceSink for WarningSink {
+    fn event(&self, _event: &TraceEvent) {}
+
+    fn rule_error(&self, error: &RuleError) {
+        self.warnings.lock().unwrap().push(error.clone());
+    }
+}
+
+#[test]
+fn count_skips_the_rule_and_reports_it() {
+    let sink = Arc::new(WarningSink::default());
+    let tokenizer = runaway_tokenizer(RegexErrorPolicy::Count).with_trace_sink(sink.clone());
+    let (tokens, errors) = tokenizer.tokenize_with_report(&runaway_text()).unwrap();
+    assert_eq!(tokens.len(), 3);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, RuleKind::Prefix);
+    assert_eq!(errors[0].chunk_start_char, 3);
+    assert!(sink.warnings.lock().unwrap().is_empty());
+}
+
+#[test]
+fn warn_and_fallback_sends_warnings_to_the_trace_sink() {
+    let sink = Arc::new(WarningSink::default());
+    let tokenizer = runaway_tokenizer(RegexErrorPolicy::WarnAndFallback).with_trace_sink(sink.clone());
+    let (tokens, errors) = tokenizer.tokenize_with_report(&runaway_text()).unwrap();
+    assert_eq!(tokens.len(), 3);
+    assert_eq!(*sink.warnings.lock().unwrap(), errors);
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn fail_aborts_tokenization() {
+    let result = runaway_tokenizer(RegexErrorPolicy::Fail).tokenize(&runaway_text());
+    assert!(matches!(result, Err(TokenizerError::RegexRuntime(ref e)) if e.kind == RuleKind::Prefix));
+}
+
+#[test]
+fn batches_report_errors_per_document() {
+    let runaway = runaway_text();
+    let texts = ["ok", runaway.as_str(), "ok ok", runaway.as_str()];
+    let tokenizer = runaway_tokenizer(RegexErrorPolicy::Count).with_batch_size(1);
+
+    let results = tokenizer.tokenize_batch_with_report(&texts).unwrap();
+    let error_counts: Vec<usize> = results.iter().map(|(_, errors)| errors.len()).collect();
+    assert_eq!(error_counts, [0, 1, 0, 1]);
+    assert_eq!(results[1].1[0].chunk_start_char, 3);
+    for (result, text) in results.iter().zip(&texts) {
+        assert_eq!(*result, tokenizer.tokenize_with_report(text).unwrap());
+    }
+
+    let docs = tokenizer.make_docs(&texts).unwrap();
+    let doc_error_counts: Vec<usize> = docs.iter().map(|doc| doc.rule_errors().len()).collect();
+    assert_eq!(doc_error_counts, error_counts);
+
+    let result = runaway_tokenizer(RegexErrorPolicy::Fail).tokenize_batch(&texts);
+    assert!(matches!(result, Err(TokenizerError::RegexRuntime(_))));
+}
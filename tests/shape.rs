@@ -0,0 +1,52 @@
+// tests/shape.rs
+//! Differential test: `SHAPE` must match spaCy's `word_shape` on the fixtures in `fixtures/`
+//! (regenerated by `fixtures/gen_shapes.py`).
+
+use my_spacy_tokenizer::lex_attrs::word_shape;
+use my_spacy_tokenizer::{Symbol, Tokenizer};
+
+const SHAPE_CHARS: &str = include_str!("fixtures/shape_chars.tsv");
+const SHAPE_WORDS: &str = include_str!("fixtures/shape_words.tsv");
+
+fn fixture_lines(fixture: &str) -> impl Iterator<Item = &str> {
+    fixture.lines().filter(|line| !line.starts_with('#'))
+}
+
+#[test]
+fn shape_of_every_char_matches_spacy() {
+    let mut mismatches = Vec::new();
+    for line in fixture_lines(SHAPE_CHARS) {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [first, last, class] = fields[..] else { panic!("bad fixture line {:?}", line) };
+        let first = u32::from_str_radix(first, 16).unwrap();
+        let last = u32::from_str_radix(last, 16).unwrap();
+        for c in (first..=last).filter_map(char::from_u32) {
+            let text = c.to_string();
+            let expected = if class == "-" { text.as_str() } else { class };
+            let shape = word_shape(&text);
+            if shape != expected {
+                mismatches.push(format!("U+{:04X} {:?}: {:?}, spaCy {:?}", c as u32, c, shape, expected));
+            }
+        }
+    }
+    assert!(mismatches.is_empty(), "{} mismatches:\n{}", mismatches.len(), mismatches.join("\n"));
+}
+
+#[test]
+fn shape_of_words_matches_spacy() {
+    for line in fixture_lines(SHAPE_WORDS) {
+        let (text, expected) = line.split_once('\t').unwrap_or_else(|| panic!("bad fixture line {:?}", line));
+        assert_eq!(word_shape(text), expected, "shape of {:?}", text);
+    }
+}
+
+#[test]
+fn tokens_expose_their_shape() {
+    let tokenizer = Tokenizer::english().unwrap();
+    let shapes: Vec<String> = tokenizer.tokenize("Apple's iPhone costs 999.99 USD")
+        .unwrap()
+        .iter()
+        .map(|t| t.get(Symbol::SHAPE).unwrap().as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(shapes, ["Xxxxx", "'x", "xXxxxx", "xxxx", "ddd.dd", "XXX"]);
+}
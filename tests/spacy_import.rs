@@ -0,0 +1,118 @@
+// tests/spacy_import.rs
+//! Import of spaCy tokenizer artifacts: the Python `re` → `fancy_regex` translation, the
+//! fallback that splits combined patterns into alternatives, and msgpack special cases.
+//!
+//! `fixtures/spacy_tokenizer.msgpack` has the layout of `Tokenizer.to_bytes(exclude=["vocab"])`
+//! (see `fixtures/gen_spacy_tokenizer.py`, which regenerates it with spaCy). The committed copy
+//! was encoded by hand in that layout, as spaCy could not be installed when it was added.
+
+use fancy_regex::Regex;
+use my_spacy_tokenizer::spacy_import::{import_spacy_tokenizer_msgpack, translate_python_regex};
+use my_spacy_tokenizer::{RuleKind, Tokenizer, TokenizerRules};
+
+const FIXTURE: &[u8] = include_bytes!("fixtures/spacy_tokenizer.msgpack");
+const FIXTURE_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/spacy_tokenizer.msgpack");
+
+/// Translates `python`, checks the result, and checks it matches like Python's `re` would.
+fn assert_translation(python: &str, expected: &str, matches: &[&str], non_matches: &[&str]) {
+    let translated = translate_python_regex(python);
+    assert_eq!(translated, expected, "translation of {:?}", python);
+    let re = Regex::new(&translated).unwrap();
+    for text in matches {
+        assert!(re.is_match(text).unwrap(), "{:?} should match {:?}", translated, text);
+    }
+    for text in non_matches {
+        assert!(!re.is_match(text).unwrap(), "{:?} should not match {:?}", translated, text);
+    }
+}
+
+#[test]
+fn end_of_string_anchor() {
+    // Python's `\Z` is the absolute end, `\z` in Rust; Rust's `\Z` does not exist.
+    assert_translation(r"km\Z", r"km\z", &["3km"], &["km\n", "kms"]);
+    // An escaped backslash followed by `Z` is not the anchor.
+    assert_translation(r"\\Z", r"\\Z", &[r"a\Z"], &["aZ"]);
+}
+
+#[test]
+fn quantifier_without_lower_bound() {
+    assert_translation(r"^a{,2}$", r"^a{0,2}$", &["", "a", "aa"], &["aaa"]);
+    assert_translation(r"^a{2,}$", r"^a{2,}$", &["aa", "aaa"], &["a"]);
+    // Braces that do not form a quantifier are literals in Python.
+    assert_translation(r"^{x}$", r"^\{x\}$", &["{x}"], &["x"]);
+    assert_translation(r"^a{,}$", r"^a{0,}$", &["", "a", "aaa"], &["a{,}"]);
+    assert_translation(r"^a{1,2,3}$", r"^a\{1,2,3\}$", &["a{1,2,3}"], &["a"]);
+}
+
+#[test]
+fn closing_bracket_first_in_class() {
+    assert_translation(r"^[]a]$", r"^[\]a]$", &["]", "a"], &["b"]);
+    assert_translation(r"^[^]a]$", r"^[^\]a]$", &["b"], &["]", "a"]);
+    // A `[` inside a class is a literal in Python, a nested class in Rust.
+    assert_translation(r"^[[(]$", r"^[\[(]$", &["[", "("], &["]"]);
+}
+
+#[test]
+fn comments_are_dropped() {
+    assert_translation(r"^a(?#the letter a)b$", r"^ab$", &["ab"], &["a b"]);
+}
+
+#[test]
+fn unicode_flag_is_dropped() {
+    assert_translation(r"(?u)^\w+$", r"^\w+$", &["café", "東京"], &["a b"]);
+}
+
+#[test]
+fn set_operators_are_literal_in_classes() {
+    // Python has no class set operations: `&&`, `--` and `~~` are plain chars.
+    assert_translation(r"^[a&&b]$", r"^[a&\&b]$", &["a", "b", "&"], &["c"]);
+    assert_translation(r"^[+--]$", r"^[+-\-]$", &["+", ",", "-"], &["a"]);
+    assert_translation(r"^[~~]$", r"^[~\~]$", &["~"], &["a"]);
+    // Outside classes they are literals in both.
+    assert_translation(r"^a&&b$", r"^a&&b$", &["a&&b"], &["ab"]);
+}
+
+#[test]
+fn msgpack_fixture_imports() {
+    let (config, report) = import_spacy_tokenizer_msgpack(FIXTURE).unwrap();
+
+    // `\N{EM DASH}` has no `fancy_regex` spelling, so the combined prefix pattern is split
+    // into its top-level alternatives (not at the `|` inside a group or class) and only that
+    // one is dropped.
+    assert_eq!(config.prefixes, [r"^\$", r"^\(", r#"^["'|]"#, r"^(?:\.\.\.|…)"]);
+    assert_eq!(report.split_patterns, [RuleKind::Prefix]);
+    assert_eq!(report.untranslated.len(), 1);
+    assert_eq!(report.untranslated[0].kind, Some(RuleKind::Prefix));
+    assert_eq!(report.untranslated[0].pattern, r"^\N{EM DASH}");
+    assert!(!report.is_lossless());
+
+    // Patterns that compile whole are kept whole.
+    assert_eq!(config.suffixes, [r"\)$|\.$|(?<=[0-9])km$"]);
+    assert_eq!(config.infixes, [r"(?<=[a-z])[,!?](?=[a-z])|(?<=[0-9])[+\-*^](?=[0-9-])"]);
+    assert_eq!(config.token_match, None);
+    assert_eq!(config.url_match.as_deref(), Some(r"^https?://\S+\z"));
+    assert!(report.translated.contains(&(r"^https?://\S+\Z".to_string(), r"^https?://\S+\z".to_string())));
+
+    // Special cases are keyed by attribute ids (ORTH=65, NORM=67) in msgpack.
+    assert_eq!(config.special_cases.len(), 2);
+    let dont = &config.special_cases["don't"];
+    assert_eq!(dont.len(), 2);
+    assert_eq!(dont[0].get("ORTH").map(String::as_str), Some("do"));
+    assert_eq!(dont[0].get("NORM"), None);
+    assert_eq!(dont[1].get("ORTH").map(String::as_str), Some("n't"));
+    assert_eq!(dont[1].get("NORM").map(String::as_str), Some("not"));
+    assert_eq!(config.special_cases["e.g."][0].get("ORTH").map(String::as_str), Some("e.g."));
+}
+
+#[test]
+fn imported_rules_tokenize() {
+    let (rules, _) = TokenizerRules::from_spacy(FIXTURE_PATH).unwrap();
+    let tokenizer = Tokenizer::new(rules);
+    let texts: Vec<String> = tokenizer
+        .tokenize("(a) don't e.g. 3km $5 a,b 1+2 ...so |x https://example.com/x")
+        .unwrap()
+        .into_iter()
+        .map(|t| t.text)
+        .collect();
+    assert_eq!(texts, ["(", "a", ")", "do", "n't", "e.g.", "3", "km", "$", "5", "a", ",", "b", "1", "+", "2", "...", "so", "|", "x", "https://example.com/x"]);
+}
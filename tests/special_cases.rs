@@ -0,0 +1,92 @@
+// tests/special_cases.rs
+//! `add_special_case` accepts what spaCy's does and rejects cases that could not tokenize
+//! their key losslessly.
+
+use std::collections::HashMap;
+
+use my_spacy_tokenizer::{Tokenizer, TokenizerError};
+
+fn piece(orth: &str) -> HashMap<String, String> {
+    HashMap::from([("ORTH".to_string(), orth.to_string())])
+}
+
+fn piece_with(orth: &str, attr: &str, value: &str) -> HashMap<String, String> {
+    let mut attrs = piece(orth);
+    attrs.insert(attr.to_string(), value.to_string());
+    attrs
+}
+
+fn texts(tokenizer: &Tokenizer, text: &str) -> Vec<String> {
+    tokenizer.tokenize(text).unwrap().into_iter().map(|t| t.text).collect()
+}
+
+#[test]
+fn valid_special_cases_are_applied_and_removed() {
+    let mut tokenizer = Tokenizer::english().unwrap();
+    assert_eq!(texts(&tokenizer, "gimme that"), ["gimme", "that"]);
+
+    tokenizer.add_special_case("gimme", vec![piece_with("gim", "NORM", "give"), piece("me")]).unwrap();
+    assert_eq!(texts(&tokenizer, "gimme that"), ["gim", "me", "that"]);
+
+    // Adding again replaces the rule.
+    tokenizer.add_special_case("gimme", vec![piece("gimm"), piece("e")]).unwrap();
+    assert_eq!(texts(&tokenizer, "gimme that"), ["gimm", "e", "that"]);
+
+    // A single piece keeps a chunk whole that the rules would split.
+    tokenizer.add_special_case("C++)", vec![piece("C++)")]).unwrap();
+    assert_eq!(texts(&tokenizer, "C++)"), ["C++)"]);
+
+    let removed = tokenizer.remove_special_case("gimme").unwrap();
+    assert_eq!(removed, [piece("gimm"), piece("e")]);
+    assert_eq!(tokenizer.remove_special_case("gimme"), None);
+    assert_eq!(texts(&tokenizer, "gimme that"), ["gimme", "that"]);
+}
+
+#[test]
+fn clones_keep_their_own_special_cases() {
+    let original = Tokenizer::english().unwrap();
+    let mut changed = original.clone();
+    changed.add_special_case("gimme", vec![piece("gim"), piece("me")]).unwrap();
+    assert_eq!(texts(&changed, "gimme"), ["gim", "me"]);
+    assert_eq!(texts(&original, "gimme"), ["gimme"]);
+}
+
+#[test]
+fn invalid_special_cases_are_rejected() {
+    let mut tokenizer = Tokenizer::english().unwrap();
+    let invalid: Vec<(&str, Vec<HashMap<String, String>>)> = vec![
+        // Pieces that do not join back to the key.
+        ("gimme", vec![piece("gim"), piece("mee")]),
+        ("gimme", vec![piece("gim")]),
+        ("gimme", vec![piece("me"), piece("gim")]),
+        // Whitespace in the key (even when the pieces join back to it).
+        ("a b", vec![piece("a"), piece(" b")]),
+        ("a\tb", vec![piece("a\tb")]),
+        ("x\u{a0}y", vec![piece("x\u{a0}y")]),
+        // Missing, empty or unsupported attributes.
+        ("gimme", vec![piece_with("gim", "LEMMA", "give"), piece("me")]),
+        ("gimme", vec![HashMap::from([("NORM".to_string(), "gimme".to_string())])]),
+        ("gimme", vec![piece(""), piece("gimme")]),
+        // Empty key or no pieces.
+        ("", vec![piece("")]),
+        ("gimme", vec![]),
+    ];
+    for (key, tokens) in invalid {
+        let result = tokenizer.add_special_case(key, tokens.clone());
+        assert!(
+            matches!(result, Err(TokenizerError::InvalidSpecialCase { key: ref k, .. }) if k == key),
+            "{:?} {:?} was accepted",
+            key,
+            tokens
+        );
+    }
+    assert_eq!(texts(&tokenizer, "gimme"), ["gimme"]);
+}
+
+#[test]
+fn adding_a_special_case_invalidates_cached_splits() {
+    let mut tokenizer = Tokenizer::english().unwrap().with_chunk_cache(1024);
+    assert_eq!(texts(&tokenizer, "gimme gimme"), ["gimme", "gimme"]);
+    tokenizer.add_special_case("gimme", vec![piece("gim"), piece("me")]).unwrap();
+    assert_eq!(texts(&tokenizer, "gimme gimme"), ["gim", "me", "gim", "me"]);
+}
@@ -0,0 +1,60 @@
+// tests/stream.rs
+//! `TokenStream` must yield exactly what `Tokenizer::tokenize` gives for the whole input,
+//! whatever the batch size, including whitespace runs that span batches.
+
+mod common;
+
+use common::XorShift;
+use my_spacy_tokenizer::{Token, Tokenizer};
+
+const BATCH_BYTES: &[usize] = &[1, 3, 7, 1 << 20];
+
+/// Pieces with multibyte letters, clusters and whitespace (ASCII and not) on both sides of newlines.
+const PIECES: &[&str] = &[
+    "word", "Straße", "東京", "e\u{301}", "😀", "👩\u{200d}💻", "don't", "(x)", "3.5km",
+    " ", "  ", "\t", "\n", "\n\n", "\r\n", " \n ", "\u{a0}", "\u{3000}", "\u{2003}\n", "\n\u{3000}",
+];
+
+fn generated_text(seed: u64, num_pieces: usize) -> String {
+    let mut rng = XorShift(seed);
+    (0..num_pieces).map(|_| PIECES[rng.below(PIECES.len())]).collect()
+}
+
+fn assert_stream_matches(tokenizer: &Tokenizer, text: &str, label: &str) {
+    let expected = tokenizer.tokenize(text).unwrap();
+    for &batch_bytes in BATCH_BYTES {
+        let mut stream = tokenizer.tokenize_reader(text.as_bytes()).with_batch_bytes(batch_bytes);
+        let streamed: Vec<Token> = stream.by_ref().collect::<Result<_, _>>().unwrap();
+        assert_eq!(streamed, expected, "batch_bytes {} on {}", batch_bytes, label);
+        assert!(stream.rule_errors().is_empty());
+    }
+}
+
+#[test]
+fn whitespace_runs_across_batches() {
+    let tokenizer = Tokenizer::english().unwrap();
+    let texts = [
+        "",
+        "\n",
+        "one\n\n\ntwo",
+        "trailing spaces   \n   \n   leading",
+        "a \n \n b\n",
+        "tabs\t\n\t\n\tand\t",
+        "crlf\r\n\r\nlines\r\n",
+        "東京\u{3000}\n\u{3000}\n\u{3000}大阪",
+        "naïve\u{a0}\n\u{a0}café\u{2003}\n",
+        "e\u{301}\n\n😀 \n 👩\u{200d}💻\n",
+        "   \n\n\t  \n",
+    ];
+    for text in texts {
+        assert_stream_matches(&tokenizer, text, &format!("{:?}", text));
+    }
+}
+
+#[test]
+fn generated_text_matches_tokenize() {
+    let tokenizer = Tokenizer::english().unwrap();
+    for seed in 1..=20 {
+        assert_stream_matches(&tokenizer, &generated_text(seed, 300), &format!("seed {}", seed));
+    }
+}
@@ -0,0 +1,104 @@
+// tests/strings.rs
+//! String ids must be the ones spaCy's `StringStore` gives: MurmurHash64A with seed 1, or the
+//! symbol's id from spaCy's `symbols.pxd`.
+
+use std::collections::HashMap;
+
+use my_spacy_tokenizer::strings::{hash_string, murmurhash64a, string_id};
+use my_spacy_tokenizer::symbols::all_symbol_names;
+use my_spacy_tokenizer::{StringStore, Symbol, Tokenizer};
+
+#[test]
+fn hashes_match_spacy() {
+    // Values printed in spaCy's documentation (`nlp.vocab.strings[...]`).
+    assert_eq!(hash_string("I"), 4690420944186131903);
+    assert_eq!(hash_string("love"), 3702023516439754181);
+    assert_eq!(hash_string("coffee"), 3197928453018144401);
+    assert_eq!(hash_string("apple"), 8566208034543834098);
+}
+
+#[test]
+fn hashes_cover_full_blocks_and_tails() {
+    // From the reference MurmurHash64A (`MurmurHash2.cpp`) with seed 1: one full 8-byte block,
+    // a block plus a tail, and multibyte UTF-8.
+    assert_eq!(hash_string("abcdefgh"), 17751780907874141760);
+    assert_eq!(hash_string("tokenization"), 15418258291467594259);
+    assert_eq!(hash_string("naïve café"), 9646334175591888092);
+    assert_eq!(hash_string("東京"), 9360021637096476946);
+    assert_eq!(murmurhash64a(b"coffee", 1), hash_string("coffee"));
+    assert_ne!(murmurhash64a(b"coffee", 2), hash_string("coffee"));
+}
+
+#[test]
+fn symbol_names_have_spacy_ids() {
+    let ids = [
+        ("NIL", 0), ("IS_ALPHA", 1), ("IS_CURRENCY", 18), ("FLAG19", 19), ("FLAG63", 63),
+        ("ID", 64), ("ORTH", 65), ("LOWER", 66), ("NORM", 67), ("SHAPE", 68), ("LEMMA", 73),
+        ("POS", 74), ("TAG", 75), ("DEP", 76), ("SPACY", 81), ("LANG", 83), ("ADJ", 84),
+        ("SPACE", 103), ("DEPRECATED001", 104), ("DEPRECATED276", 379), ("PERSON", 380),
+        ("CARDINAL", 397), ("acomp", 398), ("nsubj", 429), ("punct", 445), ("relcl", 447),
+        ("rcmod", 448), ("root", 449), ("acl", 451), ("ENT_KB_ID", 452), ("IDX", 455), ("_", 456),
+    ];
+    for (name, id) in ids {
+        assert_eq!(string_id(name), id, "id of {}", name);
+    }
+    let names = all_symbol_names();
+    assert_eq!(names.len(), 457);
+    for (i, name) in names.iter().enumerate() {
+        assert_eq!(string_id(name), i as u64, "id of {}", name);
+    }
+    assert_eq!(string_id(""), 0);
+    assert_eq!(string_id("orth"), hash_string("orth"));
+}
+
+#[test]
+fn store_interns_strings_and_resolves_symbols() {
+    let mut strings = StringStore::new();
+    assert_eq!(strings.add("nsubj"), 429);
+    assert_eq!(strings.add(""), 0);
+    assert!(strings.is_empty());
+    assert_eq!(strings.add("coffee"), 3197928453018144401);
+    assert_eq!(strings.len(), 1);
+    assert_eq!(strings.get(3197928453018144401), Some("coffee"));
+    assert_eq!(strings.get(Symbol::ORTH as u64), Some("ORTH"));
+    assert_eq!(strings.get(Symbol::DEPRECATED100 as u64), Some("DEPRECATED100"));
+    assert_eq!(strings.get(0), Some(""));
+    assert_eq!(strings.get(hash_string("tea")), None);
+    assert!(strings.contains("coffee") && strings.contains("NOUN") && !strings.contains("tea"));
+}
+
+#[test]
+fn doc_to_array_uses_string_ids() {
+    let doc = Tokenizer::english().unwrap().make_doc("I love coffee.").unwrap();
+    let mut strings = StringStore::new();
+    let array = doc.to_array(&[Symbol::ORTH, Symbol::LENGTH, Symbol::IS_PUNCT, Symbol::SPACY, Symbol::IDX, Symbol::POS], &mut strings);
+    assert_eq!(array, [
+        [4690420944186131903, 1, 0, 1, 0, 0],
+        [3702023516439754181, 4, 0, 1, 2, 0],
+        [3197928453018144401, 6, 0, 0, 7, 0],
+        [hash_string("."), 1, 1, 0, 13, 0],
+    ]);
+    assert_eq!(strings.get(array[2][0]), Some("coffee"));
+}
+
+#[test]
+fn doc_to_array_exports_special_case_norms() {
+    let mut tokenizer = Tokenizer::english().unwrap();
+    let piece = |attrs: &[(&str, &str)]| attrs.iter().map(|&(k, v)| (k.to_string(), v.to_string())).collect::<HashMap<_, _>>();
+    tokenizer.add_special_case("gimme", vec![piece(&[("ORTH", "gim"), ("NORM", "give")]), piece(&[("ORTH", "me")])]).unwrap();
+    // The second `gimme` is in the chunk `gimme.`, which no special case matches.
+
+    let doc = tokenizer.make_doc("I don't  like Coffee, gimme gimme.").unwrap();
+    let norms: Vec<String> = (0..doc.len()).map(|i| doc.norm(i).into_owned()).collect();
+    assert_eq!(norms, ["i", "do", "not", " ", "like", "coffee", ",", "give", "me", "gimme", "."]);
+
+    let mut strings = StringStore::new();
+    let array = doc.to_array(&[Symbol::NORM], &mut strings);
+    let expected: Vec<Vec<u64>> = norms.iter().map(|norm| vec![hash_string(norm)]).collect();
+    assert_eq!(array, expected);
+    assert_eq!(strings.get(array[2][0]), Some("not"));
+
+    let docs = tokenizer.make_docs(&["don't", "gimme"]).unwrap();
+    assert_eq!(docs[0].norm(1), "not");
+    assert_eq!(docs[1].norm(0), "give");
+}
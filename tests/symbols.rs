@@ -0,0 +1,78 @@
+// tests/symbols.rs
+//! `Symbol` ids must be spaCy's (`spacy/symbols.pxd`), and every view of the table must agree.
+
+use my_spacy_tokenizer::symbols::{all_symbol_names, ParseSymbolError};
+use my_spacy_tokenizer::Symbol;
+
+/// Ids from spaCy's `spacy.symbols.IDS`, at the start and end of every group of the table.
+const SPACY_IDS: &[(&str, u64)] = &[
+    ("NIL", 0),
+    ("IS_ALPHA", 1),
+    ("IS_OOV_DEPRECATED", 13),
+    ("IS_CURRENCY", 18),
+    ("FLAG19", 19),
+    ("FLAG63", 63),
+    ("ID", 64),
+    ("ORTH", 65),
+    ("LOWER", 66),
+    ("NORM", 67),
+    ("SHAPE", 68),
+    ("LEMMA", 73),
+    ("POS", 74),
+    ("TAG", 75),
+    ("DEP", 76),
+    ("ENT_TYPE", 78),
+    ("SENT_START", 80),
+    ("SPACY", 81),
+    ("LANG", 83),
+    ("ADJ", 84),
+    ("NOUN", 92),
+    ("PUNCT", 97),
+    ("SPACE", 103),
+    ("DEPRECATED001", 104),
+    ("DEPRECATED276", 379),
+    ("PERSON", 380),
+    ("ORG", 383),
+    ("CARDINAL", 397),
+    ("acomp", 398),
+    ("attr", 404),
+    ("nsubj", 429),
+    ("pobj", 439),
+    ("punct", 445),
+    ("root", 449),
+    ("acl", 451),
+    ("ENT_KB_ID", 452),
+    ("MORPH", 453),
+    ("ENT_ID", 454),
+    ("IDX", 455),
+    ("_", 456),
+];
+
+#[test]
+fn ids_match_spacy() {
+    for &(name, id) in SPACY_IDS {
+        let symbol = Symbol::from_str(name).unwrap_or_else(|| panic!("no symbol {:?}", name));
+        assert_eq!(symbol.id(), id, "id of {}", name);
+        assert_eq!(Symbol::from_id(id), Some(symbol));
+    }
+    assert_eq!(Symbol::iter().len(), 457);
+    assert_eq!(Symbol::from_id(457), None);
+}
+
+#[test]
+fn names_ids_and_order_agree() {
+    let names = all_symbol_names();
+    assert_eq!(names.len(), Symbol::iter().len());
+    for (i, symbol) in Symbol::iter().enumerate() {
+        assert_eq!(symbol.id(), i as u64);
+        assert_eq!(names[i], symbol.as_str());
+        assert_eq!(Symbol::from_str(symbol.as_str()), Some(symbol));
+        assert_eq!(symbol.to_string().parse::<Symbol>(), Ok(symbol));
+    }
+}
+
+#[test]
+fn unknown_names_do_not_parse() {
+    assert_eq!(Symbol::from_str("orth"), None);
+    assert_eq!("DEPRECATED277".parse::<Symbol>(), Err(ParseSymbolError { name: "DEPRECATED277".to_string() }));
+}
@@ -0,0 +1,56 @@
+// tests/thread_pool.rs
+//! Parallel work must run on the tokenizer's own thread pool when it has one.
+
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+
+use my_spacy_tokenizer::{TraceEvent, Tokenizer};
+
+fn documents() -> Vec<String> {
+    (0..200).map(|i| format!("Document {}: \"Hello,\" she said (again).\nLine two of {}, isn't it?", i, i)).collect()
+}
+
+/// A tokenizer whose trace sink records the name of every thread an event comes from.
+fn recording(tokenizer: Tokenizer) -> (Tokenizer, Arc<Mutex<BTreeSet<String>>>) {
+    let threads = Arc::new(Mutex::new(BTreeSet::new()));
+    let recorded = threads.clone();
+    let sink = move |_: &TraceEvent| {
+        let name = std::thread::current().name().unwrap_or("<unnamed>").to_string();
+        recorded.lock().unwrap().insert(name);
+    };
+    (tokenizer.with_trace_sink(Arc::new(sink)), threads)
+}
+
+#[test]
+fn batches_run_on_the_owned_pool() {
+    let docs = documents();
+    let texts: Vec<&str> = docs.iter().map(String::as_str).collect();
+    let plain = Tokenizer::english().unwrap();
+    let (tokenizer, threads) = recording(Tokenizer::english().unwrap().with_num_threads(3).unwrap().with_batch_size(4));
+
+    let batch = tokenizer.tokenize_batch(&texts).unwrap();
+    for (tokens, text) in batch.iter().zip(&texts) {
+        assert_eq!(*tokens, plain.tokenize(text).unwrap());
+    }
+    let threads = threads.lock().unwrap();
+    assert!(!threads.is_empty());
+    assert!(threads.iter().all(|name| name.starts_with("tokenizer-")), "{:?}", threads);
+}
+
+#[test]
+fn single_documents_run_on_a_supplied_pool() {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(2)
+        .thread_name(|i| format!("supplied-{}", i))
+        .build()
+        .unwrap();
+    let pool = Arc::new(pool);
+    let text = documents().join("\n");
+    let (tokenizer, threads) = recording(Tokenizer::english().unwrap().with_thread_pool(pool.clone()));
+
+    assert_eq!(tokenizer.tokenize(&text).unwrap(), Tokenizer::english().unwrap().tokenize(&text).unwrap());
+    assert!(Arc::ptr_eq(tokenizer.thread_pool().unwrap(), &pool));
+    let threads = threads.lock().unwrap();
+    assert!(!threads.is_empty());
+    assert!(threads.iter().all(|name| name.starts_with("supplied-")), "{:?}", threads);
+}
@@ -0,0 +1,83 @@
+// tests/trace.rs
+//! Trace events must name the stage, pattern and span of every rule match, and only be sent
+//! for traced calls.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use my_spacy_tokenizer::{CollectTraceSink, TraceEvent, TraceStage, Tokenizer, TokenizerRules};
+
+fn tokenizer() -> Tokenizer {
+    let strings = |patterns: &[&str]| patterns.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+    let piece = |orth: &str| HashMap::from([("ORTH".to_string(), orth.to_string())]);
+    let exceptions = HashMap::from([("can't".to_string(), vec![piece("ca"), piece("n't")])]);
+    let rules = TokenizerRules::from_patterns(
+        &strings(&[r"\(", r"\$"]),
+        &strings(&[r"\)", "%"]),
+        &strings(&[r"(?<=[a-z])-(?=[a-z])"]),
+        &strings(&["--"]),
+        Some(r"^\d+$"),
+        Some(r"^https?://\S+$"),
+        exceptions,
+    ).unwrap();
+    Tokenizer::new(rules)
+}
+
+#[test]
+fn events_name_stage_rule_and_span() {
+    use TraceStage::*;
+    let sink = CollectTraceSink::new();
+    tokenizer().tokenize_traced("($5%) x-y a--b can't 42 http://x.org word", &sink).unwrap();
+    let events: Vec<_> = sink.into_events().into_iter()
+        .map(|e| (e.chunk_start_char, e.stage, e.rule_index, e.span.clone(), e.matched().to_string()))
+        .collect();
+    let event = |start: usize, stage: TraceStage, index: Option<usize>, span: std::ops::Range<usize>, matched: &str| {
+        (start, stage, index, span, matched.to_string())
+    };
+    assert_eq!(events, [
+        event(0, Chunk, None, 0..5, "($5%)"),
+        event(0, Prefix, Some(0), 0..1, "("),
+        event(0, Prefix, Some(1), 1..2, "$"),
+        event(0, Suffix, Some(0), 4..5, ")"),
+        event(0, Suffix, Some(1), 3..4, "%"),
+        event(6, Chunk, None, 0..3, "x-y"),
+        event(6, Infix, Some(0), 1..2, "-"),
+        event(10, Chunk, None, 0..4, "a--b"),
+        event(10, LiteralInfix, Some(0), 1..3, "--"),
+        event(15, Chunk, None, 0..5, "can't"),
+        event(15, Special, None, 0..5, "can't"),
+        event(21, Chunk, None, 0..2, "42"),
+        event(21, TokenMatch, None, 0..2, "42"),
+        event(24, Chunk, None, 0..12, "http://x.org"),
+        event(24, UrlMatch, None, 0..12, "http://x.org"),
+        event(37, Chunk, None, 0..4, "word"),
+        event(37, Fallback, None, 0..4, "word"),
+    ]);
+}
+
+#[test]
+fn the_tokenizer_sink_gets_every_call() {
+    let sink = Arc::new(CollectTraceSink::new());
+    let tokenizer = tokenizer().with_trace_sink(sink.clone());
+    let traced = tokenizer.tokenize("(x) y").unwrap();
+    assert_eq!(traced, self::tokenizer().tokenize("(x) y").unwrap());
+    drop(tokenizer);
+    let stages: Vec<TraceStage> = Arc::try_unwrap(sink).unwrap().into_events().into_iter().map(|e| e.stage).collect();
+    assert_eq!(stages, [TraceStage::Chunk, TraceStage::Prefix, TraceStage::Suffix, TraceStage::Chunk, TraceStage::Fallback]);
+}
+
+#[test]
+fn untraced_calls_emit_nothing() {
+    let events = AtomicUsize::new(0);
+    let sink = |_: &TraceEvent| { events.fetch_add(1, Ordering::Relaxed); };
+    let tokenizer = tokenizer();
+    tokenizer.tokenize_traced("(x)", &sink).unwrap();
+    let traced_events = events.load(Ordering::Relaxed);
+    assert!(traced_events > 0);
+
+    tokenizer.tokenize("(x) y-z can't 42").unwrap();
+    tokenizer.tokenize_batch(&["(x)", "y-z"]).unwrap();
+    tokenizer.explain("(x)").unwrap();
+    assert_eq!(events.load(Ordering::Relaxed), traced_events);
+}